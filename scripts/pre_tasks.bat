@@ -1 +1,2 @@
-npx tailwindcss -i ./assets/input.css -o ./public/style.css
\ No newline at end of file
+call npx tailwindcss -i ./assets/input.css -o ./public/style.css
+cargo run --quiet --bin rusite-sitemap -- --hook
\ No newline at end of file
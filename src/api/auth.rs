@@ -0,0 +1,33 @@
+use crate::{
+    api::{client::{fetch_api, refresh_session, to_body}, error::ApiError, session},
+    models::user::{AuthToken, LoginRequest, Session, User, UserCreateRequest},
+};
+use reqwest::Method;
+
+/// 登录并持久化会话
+pub async fn login(request: &LoginRequest) -> Result<Session, ApiError> {
+    let session: Session = fetch_api("/auth/login", Method::POST, Some(to_body(request)?)).await?;
+    session::save(&session);
+    Ok(session)
+}
+
+pub async fn register(request: &UserCreateRequest) -> Result<User, ApiError> {
+    fetch_api("/auth/register", Method::POST, Some(to_body(request)?)).await
+}
+
+/// 注销。无论后端是否成功，本地会话都会被清除
+pub async fn logout() -> Result<(), ApiError> {
+    let result = fetch_api::<serde_json::Value>("/auth/logout", Method::POST, None)
+        .await
+        .map(|_| ());
+    session::clear();
+    result
+}
+
+pub async fn refresh() -> Result<AuthToken, ApiError> {
+    refresh_session().await
+}
+
+pub async fn get_current_user() -> Result<User, ApiError> {
+    fetch_api("/user/me", Method::GET, None).await
+}
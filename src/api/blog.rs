@@ -1,78 +1,142 @@
 
-use crate::{api::client::fetch_api, models::blog::{Article, ArticleInfo, Category, Comment, Tag}};
+use crate::{api::{cache::{self, CachePolicy}, client::{fetch_api, fetch_api_cached, to_body}, error::ApiError}, models::blog::{Article, ArticleCreateRequest, ArticleInfo, ArticleModifyRequest, Category, Comment, CommentCreateRequest, CommentModifyRequest, Tag}, search::index::SearchIndex};
 use reqwest::Method;
+use futures::{stream, try_join, Stream, StreamExt};
 
-pub async fn get_articles_list() -> Vec<ArticleInfo> {
-    fetch_api("/article/list", Method::GET, None).await.unwrap_or_default()
+pub(crate) const ARTICLE_LIST_PATH: &str = "/article/list";
+pub(crate) const TAGS_PATH: &str = "/article/tags";
+pub(crate) const CATEGORIES_PATH: &str = "/article/categories";
+/// 预先生成的搜索索引，只有静态导出的站点提供
+pub(crate) const SEARCH_INDEX_PATH: &str = "/search/index";
+
+pub(crate) fn content_path(aid: &str) -> String {
+    format!("/article/{aid}/content")
 }
 
-pub async fn get_article_content(aid: &str) -> String {
-    fetch_api(format!("/article/{aid}/content").as_str(), Method::GET, None).await.unwrap_or_default()
+pub(crate) fn comments_path(aid: &str) -> String {
+    format!("/article/{aid}/comment")
 }
 
-pub async fn get_article_comments(aid: &str) -> Vec<Comment> {
-    fetch_api(format!("/article/{aid}/comment").as_str(), Method::GET, None).await.unwrap_or_default()
+/// 正文缓存以文章的更新时间为版本，文章未修改时无需重新下载
+fn content_policy(info: &ArticleInfo) -> CachePolicy {
+    CachePolicy::Version(info.updated_at.to_rfc3339())
 }
 
-pub async fn get_article_details(info: ArticleInfo) -> Article {
-    let aid = &info.aid;
-    let content = get_article_content(aid).await;
-    let comments = get_article_comments(aid).await;
-    Article::new(info, &content, comments)
+pub async fn get_articles_list() -> Result<Vec<ArticleInfo>, ApiError> {
+    fetch_api_cached(ARTICLE_LIST_PATH, CachePolicy::Revalidate).await
 }
 
-pub async fn get_all_articles_details() -> Vec<Article> {
-    let infos = get_articles_list().await;
-    let mut articles = Vec::new();
-    for info in infos {
-        let article = get_article_details(info).await;
-        articles.push(article);
-    }
-    articles
+pub async fn get_article_content(info: &ArticleInfo) -> Result<String, ApiError> {
+    fetch_api_cached(&content_path(&info.aid), content_policy(info)).await
 }
 
-pub async fn get_article(aid: String) -> Article { 
-    let info = get_articles_list().await.into_iter().find(|info| info.aid == aid).unwrap();
-    let article = get_article_details(info).await;
-    article
+pub async fn get_article_comments(aid: &str) -> Result<Vec<Comment>, ApiError> {
+    fetch_api_cached(&comments_path(aid), CachePolicy::Revalidate).await
 }
 
-pub async fn get_tags() -> Vec<Tag> {
-    fetch_api("/article/tags", Method::GET, None).await.unwrap_or_default()
+pub async fn get_article_details(info: ArticleInfo) -> Result<Article, ApiError> {
+    let (content, comments) = try_join!(get_article_content(&info), get_article_comments(&info.aid))?;
+    Ok(Article::new(info, &content, comments))
 }
 
-pub async fn get_categories() -> Vec<Category> {
-    fetch_api("/article/categories", Method::GET, None).await.unwrap_or_default()
+/// 从本地缓存读取文章列表、标签和分类，不发请求
+pub fn cached_blog_data() -> Option<(Vec<ArticleInfo>, Vec<Tag>, Vec<Category>)> {
+    let policy = CachePolicy::Revalidate;
+    Some((
+        cache::peek(ARTICLE_LIST_PATH, &policy)?,
+        cache::peek(TAGS_PATH, &policy)?,
+        cache::peek(CATEGORIES_PATH, &policy)?,
+    ))
 }
 
-// TODO: 
+/// 从本地缓存还原文章详情，正文版本与 `info.updated_at` 不一致时返回 `None`
+pub fn cached_article_details(info: &ArticleInfo) -> Option<Article> {
+    let content: String = cache::peek(&content_path(&info.aid), &content_policy(info))?;
+    let comments: Vec<Comment> = cache::peek(&comments_path(&info.aid), &CachePolicy::Revalidate)?;
+    Some(Article::from_cache(info.clone(), &content, comments))
+}
 
-// use std::error::Error;
-// pub async fn get_articles_list() -> Result<Vec<ArticleInfo>, Box<dyn Error>> {
-//     fetch_api("/article/list", Method::GET, None).await
-// }
+/// 并发预取多篇文章的详情，同时进行的请求数不超过 `concurrency`。
+/// 每篇完成后立即产出结果，单篇失败不会影响其它文章。
+pub fn prefetch_articles_details(
+    infos: Vec<ArticleInfo>,
+    concurrency: usize,
+) -> impl Stream<Item = (String, Result<Article, ApiError>)> {
+    stream::iter(infos)
+        .map(|info| async move {
+            let aid = info.aid.clone();
+            (aid, get_article_details(info).await)
+        })
+        .buffer_unordered(concurrency.max(1))
+}
 
-// pub async fn get_article_content(aid: &str) -> Result<String, Box<dyn Error>> {
-//     fetch_api(format!("/article/{aid}/content").as_str(), Method::GET, None).await
-// }
+pub async fn get_article(aid: String) -> Result<Article, ApiError> {
+    let info = get_articles_list()
+        .await?
+        .into_iter()
+        .find(|info| info.aid == aid)
+        .ok_or_else(|| ApiError::not_found(format!("article {aid} not found")))?;
+    get_article_details(info).await
+}
 
-// pub async fn get_article_comments(aid: &str) -> Result<Vec<Comment>, Box<dyn Error>> {
-//     fetch_api(format!("/article/{aid}/comments").as_str(), Method::GET, None).await
-// }
+pub async fn get_tags() -> Result<Vec<Tag>, ApiError> {
+    fetch_api_cached(TAGS_PATH, CachePolicy::Revalidate).await
+}
 
-// pub async fn get_article_details(info: ArticleInfo) -> Result<Article, Box<dyn Error>> {
-//     let aid = &info.aid;
-//     let content = get_article_content(aid).await?;
-//     let comments = get_article_comments(aid).await?;
-//     Ok(Article::new(info, &content, comments))
-// }
+pub async fn get_categories() -> Result<Vec<Category>, ApiError> {
+    fetch_api_cached(CATEGORIES_PATH, CachePolicy::Revalidate).await
+}
 
-// pub async fn get_all_articles_details() -> Result<Vec<Article>, Box<dyn Error>> {
-//     let infos = get_articles_list().await?;
-//     let mut articles = Vec::new();
-//     for info in infos {
-//         let article = get_article_details(info).await?;
-//         articles.push(article);
-//     }
-//     Ok(articles)
-// }
\ No newline at end of file
+/// 下载静态导出时预先生成的搜索索引
+pub async fn get_search_index() -> Result<SearchIndex, ApiError> {
+    fetch_api(SEARCH_INDEX_PATH, Method::GET, None).await
+}
+
+/// 文章增删改之后列表、标签和分类都可能变化，丢弃它们的缓存
+fn invalidate_list_cache() {
+    cache::remove(ARTICLE_LIST_PATH);
+    cache::remove(TAGS_PATH);
+    cache::remove(CATEGORIES_PATH);
+}
+
+pub async fn create_article(request: &ArticleCreateRequest) -> Result<ArticleInfo, ApiError> {
+    let info = fetch_api("/article", Method::POST, Some(to_body(request)?)).await?;
+    invalidate_list_cache();
+    Ok(info)
+}
+
+pub async fn modify_article(request: &ArticleModifyRequest) -> Result<ArticleInfo, ApiError> {
+    let path = format!("/article/{}", request.aid);
+    let info = fetch_api(&path, Method::PUT, Some(to_body(request)?)).await?;
+    invalidate_list_cache();
+    cache::remove(&content_path(&request.aid));
+    Ok(info)
+}
+
+pub async fn delete_article(aid: &str) -> Result<(), ApiError> {
+    fetch_api::<serde_json::Value>(&format!("/article/{aid}"), Method::DELETE, None).await?;
+    invalidate_list_cache();
+    cache::remove(&content_path(aid));
+    cache::remove(&comments_path(aid));
+    Ok(())
+}
+
+pub async fn post_comment(request: &CommentCreateRequest) -> Result<Comment, ApiError> {
+    let path = comments_path(&request.aid);
+    let comment = fetch_api(&path, Method::POST, Some(to_body(request)?)).await?;
+    cache::remove(&path);
+    Ok(comment)
+}
+
+pub async fn modify_comment(aid: &str, request: &CommentModifyRequest) -> Result<Comment, ApiError> {
+    let path = format!("/comment/{}", request.cid);
+    let comment = fetch_api(&path, Method::PUT, Some(to_body(request)?)).await?;
+    cache::remove(&comments_path(aid));
+    Ok(comment)
+}
+
+pub async fn delete_comment(aid: &str, cid: &str) -> Result<(), ApiError> {
+    fetch_api::<serde_json::Value>(&format!("/comment/{cid}"), Method::DELETE, None).await?;
+    cache::remove(&comments_path(aid));
+    Ok(())
+}
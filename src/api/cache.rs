@@ -0,0 +1,192 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{api::session, utils::{storage_get, storage_remove, storage_set}};
+
+const CACHE_PREFIX: &str = "rusite:cache:";
+/// 缓存目录，记录每条缓存的写入时间，用于按先后淘汰
+const INDEX_KEY: &str = "rusite:cache-index";
+/// 最多保留的缓存条数，超出后淘汰最早写入的
+const MAX_ENTRIES: usize = 200;
+/// 超过这个天数未更新的缓存不再使用
+const MAX_AGE_DAYS: i64 = 30;
+
+/// 缓存策略
+#[derive(Debug, Clone, PartialEq)]
+pub enum CachePolicy {
+    /// 总是向后端发送条件请求（If-None-Match / If-Modified-Since），
+    /// 未修改时复用缓存；网络失败时退回到缓存
+    Revalidate,
+    /// 缓存版本与给定版本一致时直接使用缓存，不发请求。
+    /// 文章正文以 `ArticleInfo::updated_at` 作为版本
+    Version(String),
+}
+
+/// 存放在 localStorage 中的一条缓存，`data` 是 `ApiResponse::data` 的原始 JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub data: serde_json::Value,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub version: Option<String>,
+    pub stored_at: DateTime<Utc>,
+}
+
+impl CacheEntry {
+    pub fn decode<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.data.clone()).ok()
+    }
+
+    /// 缓存是否满足给定策略，满足时无需请求后端
+    pub fn satisfies(&self, policy: &CachePolicy) -> bool {
+        match policy {
+            CachePolicy::Revalidate => false,
+            CachePolicy::Version(version) => self.version.as_deref() == Some(version.as_str()),
+        }
+    }
+
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now - self.stored_at > TimeDelta::days(MAX_AGE_DAYS)
+    }
+}
+
+/// 缓存目录中的一条记录，目录按写入先后排列
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexEntry {
+    key: String,
+    path: String,
+    stored_at: DateTime<Utc>,
+}
+
+fn load_index() -> Vec<IndexEntry> {
+    storage_get(INDEX_KEY)
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &[IndexEntry]) {
+    if let Ok(raw) = serde_json::to_string(index) {
+        storage_set(INDEX_KEY, &raw);
+    }
+}
+
+/// 把新写入的缓存记到目录末尾，返回超出条数上限而需要淘汰的旧记录
+fn record(index: &mut Vec<IndexEntry>, entry: IndexEntry, max_entries: usize) -> Vec<IndexEntry> {
+    index.retain(|old| old.key != entry.key);
+    index.push(entry);
+    let excess = index.len().saturating_sub(max_entries);
+    index.drain(..excess).collect()
+}
+
+/// 已登录时按用户分区，仅对某个用户可见的响应不会被其他人读到
+fn cache_key(path: &str) -> String {
+    match session::current() {
+        Some(session) => format!("{CACHE_PREFIX}user:{}:{path}", session.user.uid),
+        None => format!("{CACHE_PREFIX}{path}"),
+    }
+}
+
+fn discard(key: &str) {
+    storage_remove(key);
+    let mut index = load_index();
+    index.retain(|entry| entry.key != key);
+    save_index(&index);
+}
+
+pub fn get(path: &str) -> Option<CacheEntry> {
+    let key = cache_key(path);
+    let raw = storage_get(&key)?;
+    match serde_json::from_str::<CacheEntry>(&raw) {
+        Ok(entry) if !entry.is_expired(Utc::now()) => Some(entry),
+        // 过期、旧格式或损坏的缓存直接丢弃
+        _ => {
+            discard(&key);
+            None
+        }
+    }
+}
+
+pub fn put(path: &str, entry: &CacheEntry) {
+    let Ok(raw) = serde_json::to_string(entry) else {
+        return;
+    };
+    let key = cache_key(path);
+    let mut index = load_index();
+    let stored = IndexEntry { key: key.clone(), path: path.to_string(), stored_at: entry.stored_at };
+    for old in record(&mut index, stored, MAX_ENTRIES) {
+        storage_remove(&old.key);
+    }
+    if !storage_set(&key, &raw) {
+        // localStorage 空间不足：淘汰较早的一半缓存后重试一次
+        let half = index.len() / 2;
+        for old in index.drain(..half) {
+            storage_remove(&old.key);
+        }
+        if !storage_set(&key, &raw) {
+            index.pop();
+            log::warn!("failed to persist cache for {path}, storage may be full");
+        }
+    }
+    save_index(&index);
+}
+
+/// 删除某个路径在所有用户分区中的缓存
+pub fn remove(path: &str) {
+    let (removed, kept): (Vec<_>, Vec<_>) = load_index().into_iter().partition(|entry| entry.path == path);
+    for entry in &removed {
+        storage_remove(&entry.key);
+    }
+    storage_remove(&cache_key(path));
+    save_index(&kept);
+}
+
+/// 读取缓存中的数据而不发请求，用于先渲染、后重新验证
+pub fn peek<T: DeserializeOwned>(path: &str, policy: &CachePolicy) -> Option<T> {
+    let entry = get(path)?;
+    match policy {
+        CachePolicy::Revalidate => entry.decode(),
+        CachePolicy::Version(_) => entry.satisfies(policy).then(|| entry.decode()).flatten(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(key: &str, secs: i64) -> IndexEntry {
+        IndexEntry {
+            key: key.to_string(),
+            path: key.to_string(),
+            stored_at: DateTime::from_timestamp(secs, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn evicts_oldest_entries_beyond_limit() {
+        let mut index = vec![stored("a", 1), stored("b", 2), stored("c", 3)];
+        let evicted = record(&mut index, stored("d", 4), 3);
+        assert_eq!(evicted, vec![stored("a", 1)]);
+        assert_eq!(index, vec![stored("b", 2), stored("c", 3), stored("d", 4)]);
+    }
+
+    #[test]
+    fn rewriting_an_entry_moves_it_to_the_end() {
+        let mut index = vec![stored("a", 1), stored("b", 2)];
+        let evicted = record(&mut index, stored("a", 3), 2);
+        assert!(evicted.is_empty());
+        assert_eq!(index, vec![stored("b", 2), stored("a", 3)]);
+    }
+
+    #[test]
+    fn expires_entries_after_max_age() {
+        let entry = CacheEntry {
+            data: serde_json::Value::Null,
+            etag: None,
+            last_modified: None,
+            version: None,
+            stored_at: DateTime::from_timestamp(0, 0).unwrap(),
+        };
+        assert!(!entry.is_expired(entry.stored_at + TimeDelta::days(MAX_AGE_DAYS)));
+        assert!(entry.is_expired(entry.stored_at + TimeDelta::days(MAX_AGE_DAYS + 1)));
+    }
+}
@@ -1,31 +1,221 @@
 use crate::{
+    api::{cache::{self, CacheEntry, CachePolicy}, error::ApiError, session},
     config::API_BASE_URL,
-    models::response::{ApiResponse, ErrorResponse}
+    models::{response::{ApiResponse, ErrorResponse}, user::{AuthToken, RefreshRequest}}
 };
-use std::error::Error;
-use reqwest::Client;
+use std::{cell::RefCell, time::Duration};
+use chrono::Utc;
+use futures::{future::Shared, FutureExt};
+use reqwest::{header, Client, Method, RequestBuilder, Response, StatusCode};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const REFRESH_PATH: &str = "/auth/refresh";
+
+// 浏览器中的请求不是 `Send`，服务端渲染时则需要 `Send` 才能在 tokio 上运行
+#[cfg(target_arch = "wasm32")]
+type RefreshFuture = Shared<futures::future::LocalBoxFuture<'static, Result<AuthToken, ApiError>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+type RefreshFuture = Shared<futures::future::BoxFuture<'static, Result<AuthToken, ApiError>>>;
+
+thread_local! {
+    // 正在进行的令牌刷新。refresh token 只能使用一次，并发收到的 401 共用同一次刷新
+    static REFRESHING: RefCell<Option<RefreshFuture>> = const { RefCell::new(None) };
+}
 
 pub async fn fetch_api<T: serde::de::DeserializeOwned>(
     path: &str,
     method: reqwest::Method,
     body: Option<serde_json::Value>,
-) -> Result<T, Box<dyn Error>> {
-    let client = Client::new();
-    let url = format!("{}{}", API_BASE_URL, path);
-    
-    let mut request = client.request(method, &url);
+) -> Result<T, ApiError> {
+    let response = send_authorized(path, method, body.as_ref(), |request| request).await?;
+    decode_response(response).await
+}
+
+/// 序列化请求体
+pub fn to_body<T: serde::Serialize>(value: &T) -> Result<serde_json::Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::Decode(e.to_string()))
+}
 
+/// 构造请求，已登录时自动附带 `Authorization: Bearer <token>`
+fn build_request(path: &str, method: Method, body: Option<&serde_json::Value>) -> RequestBuilder {
+    let url = format!("{}{}", API_BASE_URL, path);
+    let mut request = Client::new().request(method, &url).timeout(REQUEST_TIMEOUT);
+    if let Some(token) = session::access_token() {
+        request = request.bearer_auth(token);
+    }
     if let Some(body) = body {
-        request = request.json(&body);
+        request = request.json(body);
+    }
+    request
+}
+
+/// 发送请求。收到 401 时先尝试用 refresh token 换取新令牌并重发一次，
+/// 仍然失败则清除会话。
+async fn send_authorized(
+    path: &str,
+    method: Method,
+    body: Option<&serde_json::Value>,
+    extra: impl Fn(RequestBuilder) -> RequestBuilder,
+) -> Result<Response, ApiError> {
+    let had_token = session::access_token().is_some();
+    let response = extra(build_request(path, method.clone(), body)).send().await?;
+    if response.status() != StatusCode::UNAUTHORIZED || !had_token {
+        return Ok(response);
     }
+    if session::refresh_token().is_some() && refresh_session().await.is_ok() {
+        let response = extra(build_request(path, method, body)).send().await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+    }
+    session::expire();
+    Ok(response)
+}
+
+/// 用 refresh token 换取新的访问令牌并写回会话，已有刷新在进行时等待其结果
+pub async fn refresh_session() -> Result<AuthToken, ApiError> {
+    let refresh = REFRESHING.with_borrow_mut(|refreshing| {
+        refreshing
+            .get_or_insert_with(|| boxed(request_refresh()).shared())
+            .clone()
+    });
+    refresh.await
+}
+
+#[cfg(target_arch = "wasm32")]
+fn boxed(refresh: impl Future<Output = Result<AuthToken, ApiError>> + 'static) -> futures::future::LocalBoxFuture<'static, Result<AuthToken, ApiError>> {
+    refresh.boxed_local()
+}
 
-    let response = request.send().await?;
+#[cfg(not(target_arch = "wasm32"))]
+fn boxed(refresh: impl Future<Output = Result<AuthToken, ApiError>> + Send + 'static) -> futures::future::BoxFuture<'static, Result<AuthToken, ApiError>> {
+    refresh.boxed()
+}
 
-    if response.status().is_success() {
-        let api_response: ApiResponse<T> = response.json().await?;
+async fn request_refresh() -> Result<AuthToken, ApiError> {
+    let result = send_refresh().await;
+    REFRESHING.with_borrow_mut(|refreshing| *refreshing = None);
+    result
+}
+
+async fn send_refresh() -> Result<AuthToken, ApiError> {
+    let refresh_token = session::refresh_token()
+        .ok_or_else(|| ApiError::Unauthorized("no refresh token".to_string()))?;
+    let body = to_body(&RefreshRequest { refresh_token })?;
+    let url = format!("{}{}", API_BASE_URL, REFRESH_PATH);
+    let response = Client::new()
+        .post(&url)
+        .timeout(REQUEST_TIMEOUT)
+        .json(&body)
+        .send()
+        .await?;
+    let token: AuthToken = decode_response(response).await?;
+    session::update_token(token.clone());
+    Ok(token)
+}
+
+/// 带本地缓存的 GET 请求。
+///
+/// 缓存满足 `policy` 时直接返回；否则带上缓存的 ETag / Last-Modified 发起条件请求，
+/// 304 时复用缓存，200 时更新缓存。请求失败但存在缓存时返回（可能过期的）缓存数据。
+/// 跨域部署时后端需要通过 `Access-Control-Expose-Headers` 暴露 `ETag` 和 `Last-Modified`。
+/// 已登录时缓存按用户分区存放，不同用户互不可见。
+pub async fn fetch_api_cached<T: serde::de::DeserializeOwned>(
+    path: &str,
+    policy: CachePolicy,
+) -> Result<T, ApiError> {
+    let cached = cache::get(path);
+    if let Some(entry) = cached.as_ref().filter(|entry| entry.satisfies(&policy))
+        && let Some(data) = entry.decode()
+    {
+        return Ok(data);
+    }
+
+    let conditional = |mut request: RequestBuilder| {
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        request
+    };
+
+    let response = match send_authorized(path, Method::GET, None, conditional).await {
+        Ok(response) => response,
+        Err(err) => {
+            return cached
+                .and_then(|entry| entry.decode())
+                .ok_or(err);
+        }
+    };
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let Some(mut entry) = cached
+            && let Some(data) = entry.decode()
+        {
+            entry.stored_at = Utc::now();
+            entry.version = version_of(&policy).or(entry.version);
+            cache::put(path, &entry);
+            return Ok(data);
+        }
+        // 缓存已被清除却收到 304，去掉条件头重新请求
+        cache::remove(path);
+        return fetch_api(path, Method::GET, None).await;
+    }
+
+    let etag = header_value(&response, header::ETAG);
+    let last_modified = header_value(&response, header::LAST_MODIFIED);
+    let data: serde_json::Value = match decode_response(response).await {
+        Ok(data) => data,
+        Err(err) if err.is_retryable() => {
+            return cached
+                .and_then(|entry| entry.decode())
+                .ok_or(err);
+        }
+        Err(err) => return Err(err),
+    };
+    let value = serde_json::from_value(data.clone())
+        .map_err(|e| ApiError::Decode(e.to_string()))?;
+    cache::put(path, &CacheEntry {
+        data,
+        etag,
+        last_modified,
+        version: version_of(&policy),
+        stored_at: Utc::now(),
+    });
+    Ok(value)
+}
+
+async fn decode_response<T: serde::de::DeserializeOwned>(response: Response) -> Result<T, ApiError> {
+    let status = response.status();
+    if status.is_success() {
+        let api_response: ApiResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Decode(e.to_string()))?;
         Ok(api_response.data)
     } else {
-        let error_response: ErrorResponse = response.json().await?;
-        Err(error_response.message.into())
+        // 错误响应体不一定是 ErrorResponse（例如网关返回的 HTML），解析失败时退回状态码
+        let error_response = response.json::<ErrorResponse>().await.ok();
+        Err(ApiError::from_status(status, error_response))
+    }
+}
+
+fn header_value(response: &Response, name: header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn version_of(policy: &CachePolicy) -> Option<String> {
+    match policy {
+        CachePolicy::Revalidate => None,
+        CachePolicy::Version(version) => Some(version.clone()),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,106 @@
+use std::{error::Error, fmt};
+
+use reqwest::StatusCode;
+
+use crate::models::response::ErrorResponse;
+
+/// 请求后端 API 时可能出现的错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// 网络不可达、连接被重置等传输层错误
+    Network(String),
+    /// 后端返回了非 2xx 状态码
+    Http {
+        status: u16,
+        code: u16,
+        message: String,
+    },
+    /// 响应体无法解析为期望的结构
+    Decode(String),
+    /// 请求超时
+    Timeout,
+    /// 未登录或登录已失效（401）
+    Unauthorized(String),
+}
+
+impl ApiError {
+    /// 根据状态码和（可能缺失的）错误响应体构造错误
+    pub fn from_status(status: StatusCode, body: Option<ErrorResponse>) -> Self {
+        let message = body
+            .as_ref()
+            .map(|e| e.message.clone())
+            .unwrap_or_else(|| status.canonical_reason().unwrap_or("Unknown error").to_string());
+        if status == StatusCode::UNAUTHORIZED {
+            return ApiError::Unauthorized(message);
+        }
+        ApiError::Http {
+            status: status.as_u16(),
+            code: body.map(|e| e.code).unwrap_or(status.as_u16()),
+            message,
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        ApiError::Http {
+            status: StatusCode::NOT_FOUND.as_u16(),
+            code: StatusCode::NOT_FOUND.as_u16(),
+            message: message.into(),
+        }
+    }
+
+    /// 重试是否有可能成功（网络抖动、超时、服务端 5xx）
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Network(_) | ApiError::Timeout => true,
+            ApiError::Http { status, .. } => *status >= 500,
+            ApiError::Decode(_) | ApiError::Unauthorized(_) => false,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ApiError::Http { status: 404, .. })
+    }
+
+    /// 展示给读者的简短标题
+    pub fn title(&self) -> &'static str {
+        match self {
+            ApiError::Network(_) => "无法连接到服务器",
+            ApiError::Http { status, .. } if *status >= 500 => "服务器出错了",
+            ApiError::Http { status: 404, .. } => "内容不存在",
+            ApiError::Http { .. } => "请求失败",
+            ApiError::Decode(_) => "数据格式错误",
+            ApiError::Timeout => "请求超时",
+            ApiError::Unauthorized(_) => "需要登录",
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(msg) => write!(f, "network error: {msg}"),
+            ApiError::Http { status, code, message } => {
+                write!(f, "http {status} (code {code}): {message}")
+            }
+            ApiError::Decode(msg) => write!(f, "decode error: {msg}"),
+            ApiError::Timeout => write!(f, "request timed out"),
+            ApiError::Unauthorized(msg) => write!(f, "unauthorized: {msg}"),
+        }
+    }
+}
+
+impl Error for ApiError {}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            ApiError::Timeout
+        } else if err.is_decode() {
+            ApiError::Decode(err.to_string())
+        } else if let Some(status) = err.status() {
+            ApiError::from_status(status, None)
+        } else {
+            ApiError::Network(err.to_string())
+        }
+    }
+}
@@ -1,3 +1,6 @@
 pub mod blog;
 pub mod auth;
-pub mod client;
\ No newline at end of file
+pub mod cache;
+pub mod client;
+pub mod error;
+pub mod session;
\ No newline at end of file
@@ -0,0 +1,79 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    models::user::{AuthToken, Session},
+    utils::{storage_get, storage_remove, storage_set},
+};
+
+const SESSION_KEY: &str = "rusite:session";
+
+type UnauthorizedHandler = Rc<dyn Fn()>;
+
+thread_local! {
+    // 当前会话在内存中的副本，`fetch_api` 每次请求时从这里读取令牌
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+    // 会话失效（401 且刷新失败）时的回调，由 `AuthState` 注册
+    static ON_UNAUTHORIZED: RefCell<Option<UnauthorizedHandler>> = const { RefCell::new(None) };
+}
+
+fn set_session(session: Option<Session>) {
+    SESSION.with(|current| *current.borrow_mut() = session);
+}
+
+fn with_session<T>(f: impl FnOnce(&Session) -> Option<T>) -> Option<T> {
+    SESSION.with(|current| current.borrow().as_ref().and_then(f))
+}
+
+/// 从 localStorage 恢复上次的会话
+pub fn restore() -> Option<Session> {
+    let session: Session = storage_get(SESSION_KEY)
+        .and_then(|raw| serde_json::from_str(&raw).ok())?;
+    set_session(Some(session.clone()));
+    Some(session)
+}
+
+pub fn save(session: &Session) {
+    if let Ok(raw) = serde_json::to_string(session) {
+        storage_set(SESSION_KEY, &raw);
+    }
+    set_session(Some(session.clone()));
+}
+
+/// 更新令牌（刷新后调用），保留当前用户
+pub fn update_token(token: AuthToken) {
+    if let Some(mut session) = current() {
+        session.token = token;
+        save(&session);
+    }
+}
+
+pub fn clear() {
+    storage_remove(SESSION_KEY);
+    set_session(None);
+}
+
+pub fn current() -> Option<Session> {
+    with_session(|session| Some(session.clone()))
+}
+
+pub fn access_token() -> Option<String> {
+    with_session(|session| Some(session.token.access_token.clone()))
+}
+
+pub fn refresh_token() -> Option<String> {
+    with_session(|session| session.token.refresh_token.clone())
+}
+
+pub fn set_unauthorized_handler(handler: impl Fn() + 'static) {
+    ON_UNAUTHORIZED.with(|current| *current.borrow_mut() = Some(Rc::new(handler)));
+}
+
+/// 会话已失效：清除本地会话并通知 `AuthState`
+pub fn expire() {
+    clear();
+    // 先取出回调再调用，回调里可以重新注册
+    let handler = ON_UNAUTHORIZED.with(|current| current.borrow().clone());
+    if let Some(handler) = handler {
+        handler();
+    }
+}
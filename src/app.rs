@@ -2,16 +2,22 @@ use leptos::{prelude::*, task::spawn_local};
 use leptos_meta::*;
 use leptos_router::{
     components::{
-        Route, Router, Routes
+        ProtectedRoute, Route, Router, Routes
     },
+    hooks::use_location,
     path
 };
 use crate::{
     components::{layout::{
         backdrop::Backdrop, footer::Footer, header::Header
     }, ui::panle::SearchPanle},
+    highlight::theme_css,
+    seo::{title_template, SITE_NAME},
     pages::{
         about::AboutPage,
+        archive::ArchivePage,
+        admin::{dashboard::AdminDashboard, editor::ArticleEditor},
+        auth::{login::{login_path, LoginPage}, register::RegisterPage},
         blog::{article::{
             ArticleDital,
         }, index::BlogIndex}, 
@@ -19,22 +25,40 @@ use crate::{
     },
     state::{
         provide_app_context,
+        provide_auth_context,
         use_app,
+        use_auth,
     }
 };
 
+/// 可以被搜索引擎收录的固定路由，修改下面的 `Routes` 时同步更新
+pub const STATIC_ROUTES: &[&str] = &["/", "/blog", "/archive", "/about", "/chat"];
+/// 需要登录或与账号相关的路由，不允许抓取
+pub const PRIVATE_ROUTES: &[&str] = &["/admin", "/user", "/login", "/register"];
+
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
     provide_app_context();
+    provide_auth_context();
 
     let state = use_app();
+    let auth = use_auth();
+    // 放在 Effect 中，只在浏览器中执行，服务端渲染时不发请求
     let state_clone = state.clone();
-    spawn_local(async move {
-        state_clone.load_data().await;
+    Effect::new(move |_| {
+        let state = state_clone.clone();
+        spawn_local(async move {
+            state.load_data().await;
+        });
+        spawn_local(async move {
+            auth.restore().await;
+        });
     });
     
     view! {
+        <Title formatter=title_template text=SITE_NAME />
+        <Style id="code-highlight">{theme_css()}</Style>
         <Router>
             <div>
                 <Backdrop show=state.active />
@@ -47,9 +71,18 @@ pub fn App() -> impl IntoView {
                         <Route path=path!("/") view=HomePage />
                         <Route path=path!("/blog") view=BlogIndex />
                         <Route path=path!("/blog/:id") view=ArticleDital />
+                        <Route path=path!("/archive") view=ArchivePage />
                         <Route path=path!("/about") view=AboutPage />
                         <Route path=path!("/chat") view=ChatGroupList />
                         <Route path=path!("/user") view=UserProfilePage />
+                        <Route path=path!("/login") view=LoginPage />
+                        <Route path=path!("/register") view=RegisterPage />
+                        <ProtectedRoute path=path!("/admin") view=AdminDashboard
+                            condition=move || auth.logged_in() redirect_path=redirect_to_login />
+                        <ProtectedRoute path=path!("/admin/new") view=ArticleEditor
+                            condition=move || auth.logged_in() redirect_path=redirect_to_login />
+                        <ProtectedRoute path=path!("/admin/edit/:id") view=ArticleEditor
+                            condition=move || auth.logged_in() redirect_path=redirect_to_login />
                     </Routes>
                 </main>
             </div>
@@ -57,3 +90,8 @@ pub fn App() -> impl IntoView {
         </Router>
     }
 }
+
+/// 未登录访问受保护页面时跳转登录页，登录后回到当前页面
+fn redirect_to_login() -> String {
+    login_path(&use_location().pathname.get_untracked())
+}
@@ -0,0 +1,170 @@
+//! 导出可以部署到静态托管的完整站点：预渲染 `app.rs` 中的所有页面和文章页，
+//! 并写入只读接口的静态镜像、预先生成的搜索索引、订阅源、站点地图和 robots.txt，浏览器加载 WASM 后接管页面。
+//!
+//! ```text
+//! trunk build --release --no-default-features --features hydrate
+//! cargo run --release --no-default-features --features ssr --bin rusite-export -- [--markdown posts] [--out dist]
+//! ```
+//!
+//! 默认通过 `api::blog` 从后端拉取文章；指定 `--markdown` 时读取目录中带 Front Matter 的 `.md` 文件，
+//! 文件名即文章 id。`--out` 为 trunk 的输出目录（默认 `dist`），其中的 `index.html` 作为页面模板。
+//! 浏览器端仍按 `config.rs` 中的 `API_BASE_URL` 请求数据，构建时将其设置为 `站点地址/api` 即可使用导出的接口。
+//! 站点信息的环境变量与 `rusite-feeds` 相同。
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use any_spawner::Executor;
+use chrono::{DateTime, Utc};
+use futures::{try_join, StreamExt};
+use rusite_front_ending::{
+    api::blog::{get_articles_list, get_categories, get_tags, prefetch_articles_details},
+    export::{page_file, ExportedArticle, SiteData, NOT_FOUND_FILE},
+    feeds::{Feed, FeedConfig, FeedEntry},
+    sitemap::{collect_urls, render_robots, render_sitemap, ROBOTS_FILE, SITEMAP_FILE},
+    ssr::render_page,
+    state::PRELOADED_ID,
+};
+
+/// 同时进行的正文请求数
+const CONCURRENCY: usize = 4;
+
+#[tokio::main]
+async fn main() {
+    if let Err(error) = run().await {
+        eprintln!("rusite-export: {error}");
+        std::process::exit(1);
+    }
+}
+
+struct Args {
+    markdown: Option<PathBuf>,
+    out: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { markdown: None, out: PathBuf::from("dist") };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("{arg} 缺少参数值"));
+        match arg.as_str() {
+            "--markdown" => args.markdown = Some(value()?.into()),
+            "--out" => args.out = value()?.into(),
+            _ => return Err(format!("未知参数 {arg}")),
+        }
+    }
+    Ok(args)
+}
+
+async fn fetch_site() -> Result<SiteData, String> {
+    let (infos, tags, categories) =
+        try_join!(get_articles_list(), get_tags(), get_categories()).map_err(|e| e.to_string())?;
+    // 设置了访问密码的文章不导出正文
+    let public = infos.iter().filter(|info| info.secret.is_none()).cloned().collect();
+    let mut details = prefetch_articles_details(public, CONCURRENCY)
+        .map(|(aid, result)| result.map(|article| (aid.clone(), article)).map_err(|e| format!("{aid}: {e}")))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    let articles = infos
+        .into_iter()
+        .map(|info| match details.remove(&info.aid) {
+            Some(article) => ExportedArticle {
+                content: Some(article.content().to_string()),
+                comments: article.comments().to_vec(),
+                info,
+            },
+            None => ExportedArticle { info, content: None, comments: Vec::new() },
+        })
+        .collect();
+    Ok(SiteData { articles, tags, categories })
+}
+
+fn read_markdown(dir: &Path) -> Result<SiteData, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    let mut articles = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| format!("{}: {e}", dir.display()))?.path();
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let Some(aid) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let markdown = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        articles.push(ExportedArticle::from_markdown(aid, &markdown, modified));
+    }
+    articles.sort_by_key(|article| std::cmp::Reverse(article.info.created_at));
+    Ok(SiteData::new(articles))
+}
+
+fn write(out: &Path, name: &str, content: &str) -> Result<(), String> {
+    let path = out.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+    fs::write(&path, content).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+async fn run() -> Result<(), String> {
+    dotenv::dotenv().ok();
+    let args = parse_args()?;
+    Executor::init_tokio().map_err(|e| e.to_string())?;
+
+    let index = args.out.join("index.html");
+    let template = fs::read_to_string(&index).map_err(|e| format!("{}: {e}，请先运行 trunk build", index.display()))?;
+    // 首页会覆盖 index.html，再次导出前需要重新生成模板
+    if template.contains(PRELOADED_ID) {
+        return Err(format!("{} 已经是导出的页面，请先重新运行 trunk build", index.display()));
+    }
+    let site = match &args.markdown {
+        Some(dir) => read_markdown(dir)?,
+        None => fetch_site().await?,
+    };
+
+    let routes = site.routes();
+    for route in &routes {
+        let page = render_page(&template, route, Some(site.preloaded(route)))
+            .await
+            .map_err(|e| format!("{route}: {e}"))?;
+        write(&args.out, &page_file(route), &page)?;
+    }
+    let page = render_page(&template, "/404", Some(site.preloaded("/404")))
+        .await
+        .map_err(|e| format!("/404: {e}"))?;
+    write(&args.out, NOT_FOUND_FILE, &page)?;
+
+    let api_files = site.api_files().map_err(|e| format!("api: {e}"))?;
+    for (name, content) in &api_files {
+        write(&args.out, name, content)?;
+    }
+
+    let config = FeedConfig::from_env();
+    let site_url = config.site_url.clone();
+    let entries = site
+        .articles
+        .iter()
+        .map(|article| FeedEntry::new(&config, &article.info, article.content.as_deref()))
+        .collect();
+    for (name, content) in Feed::new(config, entries).render_all() {
+        write(&args.out, name, &content)?;
+    }
+    write(&args.out, SITEMAP_FILE, &render_sitemap(&collect_urls(&site_url, &site.infos())))?;
+    write(&args.out, ROBOTS_FILE, &render_robots(&site_url))?;
+
+    println!(
+        "exported {} pages and {} api files to {}",
+        routes.len() + 1,
+        api_files.len(),
+        args.out.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! 生成 RSS、Atom 和 JSON Feed 订阅源并写入输出目录。
+//!
+//! ```text
+//! rusite-feeds [--input articles.json] [--out dist] [--hook]
+//! ```
+//!
+//! 默认通过 `api::blog` 从后端拉取文章；指定 `--input` 时读取本地导出的 JSON，
+//! 格式为 `ArticleInfo` 数组，每项可以额外带一个 `content` 字段存放 Markdown 正文。
+//! 站点信息从环境变量（或 `.env`）读取：`SITE_URL`、`SITE_TITLE`、`SITE_DESCRIPTION`、`SITE_AUTHOR`。
+//! 作为 trunk 的 `post_build` 钩子运行时，默认写入 `TRUNK_STAGING_DIR`。
+//! 带 `--hook` 时只有设置了 `SITE_FETCH_ON_BUILD` 才会生成，避免没有后端时构建失败。
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::main]
+async fn main() {
+    if let Err(error) = cli::run().await {
+        eprintln!("rusite-feeds: {error}");
+        std::process::exit(1);
+    }
+}
+
+// 只在构建时运行，不参与 WASM 构建
+#[cfg(target_arch = "wasm32")]
+fn main() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod cli {
+    use std::{env, fs, path::PathBuf};
+
+    use futures::{stream, StreamExt};
+    use rusite_front_ending::{
+        api::blog::{get_article_content, get_articles_list},
+        feeds::{Feed, FeedConfig, FeedEntry},
+        models::blog::ArticleInfo,
+    };
+    use serde::Deserialize;
+
+    /// 同时进行的正文请求数
+    const CONCURRENCY: usize = 4;
+
+    /// 本地导出文件中的一篇文章
+    #[derive(Debug, Deserialize)]
+    struct DumpedArticle {
+        #[serde(flatten)]
+        info: ArticleInfo,
+        #[serde(default)]
+        content: Option<String>,
+    }
+
+    struct Args {
+        input: Option<PathBuf>,
+        out: PathBuf,
+        /// 作为 trunk 钩子运行
+        hook: bool,
+    }
+
+    fn parse_args() -> Result<Args, String> {
+        let out = env::var("TRUNK_STAGING_DIR").unwrap_or_else(|_| "dist".to_string());
+        let mut args = Args { input: None, out: PathBuf::from(out), hook: false };
+        let mut iter = env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            let mut value = || iter.next().ok_or_else(|| format!("{arg} 缺少参数值"));
+            match arg.as_str() {
+                "--input" => args.input = Some(value()?.into()),
+                "--out" => args.out = value()?.into(),
+                "--hook" => args.hook = true,
+                _ => return Err(format!("未知参数 {arg}")),
+            }
+        }
+        Ok(args)
+    }
+
+    async fn fetch_articles() -> Result<Vec<(ArticleInfo, Option<String>)>, String> {
+        let infos = get_articles_list().await.map_err(|e| e.to_string())?;
+        stream::iter(infos)
+            .map(|info| async move {
+                // 设置了访问密码的文章不输出正文，无需下载
+                if info.secret.is_some() {
+                    return Ok((info, None));
+                }
+                let content = get_article_content(&info).await.map_err(|e| format!("{}: {e}", info.aid))?;
+                Ok((info, Some(content)))
+            })
+            .buffer_unordered(CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    fn read_dump(path: &PathBuf) -> Result<Vec<(ArticleInfo, Option<String>)>, String> {
+        let raw = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let articles: Vec<DumpedArticle> = serde_json::from_str(&raw).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(articles.into_iter().map(|article| (article.info, article.content)).collect())
+    }
+
+    pub async fn run() -> Result<(), String> {
+        dotenv::dotenv().ok();
+        let args = parse_args()?;
+        if args.hook && env::var_os("SITE_FETCH_ON_BUILD").is_none() {
+            println!("rusite-feeds: SITE_FETCH_ON_BUILD is not set, skipped");
+            return Ok(());
+        }
+        let articles = match &args.input {
+            Some(path) => read_dump(path)?,
+            None => fetch_articles().await?,
+        };
+
+        let config = FeedConfig::from_env();
+        let entries = articles
+            .iter()
+            .map(|(info, content)| FeedEntry::new(&config, info, content.as_deref()))
+            .collect();
+        let feed = Feed::new(config, entries);
+
+        fs::create_dir_all(&args.out).map_err(|e| format!("{}: {e}", args.out.display()))?;
+        for (name, content) in feed.render_all() {
+            let path = args.out.join(name);
+            fs::write(&path, content).map_err(|e| format!("{}: {e}", path.display()))?;
+            println!("wrote {}", path.display());
+        }
+        Ok(())
+    }
+}
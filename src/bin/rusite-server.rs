@@ -0,0 +1,117 @@
+//! 服务端渲染服务器：每个页面请求先在服务端渲染成 HTML，并嵌入预加载的数据，
+//! 浏览器加载 WASM 后直接水合，无需再等待文章列表请求。
+//!
+//! ```text
+//! trunk build --release --no-default-features --features hydrate
+//! cargo run --release --no-default-features --features ssr --bin rusite-server
+//! ```
+//!
+//! 静态资源取自 trunk 的输出目录 `SITE_ROOT`（默认 `dist`），其中的 `index.html` 作为页面模板。
+//! 监听地址读取 `SITE_ADDR`，默认 `127.0.0.1:3000`。
+
+use std::{
+    env, fs,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use any_spawner::Executor;
+use axum::{
+    extract::State,
+    http::{header, StatusCode, Uri},
+    response::{Html, IntoResponse, Response},
+    Router,
+};
+use rusite_front_ending::{ssr::render_page, state::Preloaded};
+
+#[tokio::main]
+async fn main() {
+    if let Err(error) = run().await {
+        eprintln!("rusite-server: {error}");
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), String> {
+    dotenv::dotenv().ok();
+    Executor::init_tokio().map_err(|e| e.to_string())?;
+
+    let root = PathBuf::from(env::var("SITE_ROOT").unwrap_or_else(|_| "dist".to_string()));
+    let index = root.join("index.html");
+    let template = fs::read_to_string(&index).map_err(|e| format!("{}: {e}，请先运行 trunk build", index.display()))?;
+    let site = Arc::new(Site { root, template });
+
+    let addr = env::var("SITE_ADDR").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
+    let listener = tokio::net::TcpListener::bind(&addr).await.map_err(|e| format!("{addr}: {e}"))?;
+    println!("listening on http://{addr}");
+    let app = Router::new().fallback(handle).with_state(site);
+    axum::serve(listener, app).await.map_err(|e| e.to_string())
+}
+
+/// trunk 的输出目录和页面模板
+struct Site {
+    root: PathBuf,
+    template: String,
+}
+
+async fn handle(State(site): State<Arc<Site>>, uri: Uri) -> Response {
+    match site.static_file(uri.path()).await {
+        Some(response) => response,
+        None => site.render(&uri).await,
+    }
+}
+
+impl Site {
+    /// 输出目录中存在的文件直接返回，其余路径交给前端路由渲染
+    async fn static_file(&self, path: &str) -> Option<Response> {
+        let relative = Path::new(path.trim_start_matches('/'));
+        // 拒绝 `..` 等跳出输出目录的路径；页面模板不直接提供
+        if relative.as_os_str().is_empty()
+            || relative == Path::new("index.html")
+            || !relative.components().all(|component| matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+        let bytes = tokio::fs::read(self.root.join(relative)).await.ok()?;
+        Some(([(header::CONTENT_TYPE, content_type(relative))], bytes).into_response())
+    }
+
+    async fn render(&self, uri: &Uri) -> Response {
+        let url = uri.path_and_query().map(|url| url.as_str()).unwrap_or("/");
+        // 后端不可用时仍然渲染页面，由浏览器重新加载数据
+        let preloaded = match Preloaded::fetch(uri.path()).await {
+            Ok(preloaded) => Some(preloaded),
+            Err(err) => {
+                eprintln!("rusite-server: failed to preload {url}: {err}");
+                None
+            }
+        };
+        match render_page(&self.template, url, preloaded).await {
+            Ok(page) => Html(page).into_response(),
+            Err(err) => {
+                eprintln!("rusite-server: failed to render {url}: {err}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript",
+        Some("wasm") => "application/wasm",
+        Some("css") => "text/css",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("woff2") => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
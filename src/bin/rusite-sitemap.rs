@@ -0,0 +1,76 @@
+//! 生成 `sitemap.xml` 和 `robots.txt` 并写入输出目录。
+//!
+//! ```text
+//! rusite-sitemap [--out dist] [--hook]
+//! ```
+//!
+//! 文章列表通过 `api::blog::get_articles_list` 从后端获取，站点地址从环境变量（或 `.env`）
+//! 的 `SITE_URL` 读取。在 trunk 的 `pre_build` 钩子中运行时默认写入 `TRUNK_STAGING_DIR`。
+//! 带 `--hook` 时只有设置了 `SITE_FETCH_ON_BUILD` 才会生成，避免没有后端时构建失败。
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::main]
+async fn main() {
+    if let Err(error) = cli::run().await {
+        eprintln!("rusite-sitemap: {error}");
+        std::process::exit(1);
+    }
+}
+
+// 只在构建时运行，不参与 WASM 构建
+#[cfg(target_arch = "wasm32")]
+fn main() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod cli {
+    use std::{env, fs, path::PathBuf};
+
+    use rusite_front_ending::{
+        api::blog::get_articles_list,
+        seo::site_url,
+        sitemap::{collect_urls, render_robots, render_sitemap, ROBOTS_FILE, SITEMAP_FILE},
+    };
+
+    struct Args {
+        out: PathBuf,
+        /// 作为 trunk 钩子运行
+        hook: bool,
+    }
+
+    fn parse_args() -> Result<Args, String> {
+        let out = env::var("TRUNK_STAGING_DIR").unwrap_or_else(|_| "dist".to_string());
+        let mut args = Args { out: PathBuf::from(out), hook: false };
+        let mut iter = env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--out" => args.out = iter.next().ok_or_else(|| format!("{arg} 缺少参数值"))?.into(),
+                "--hook" => args.hook = true,
+                _ => return Err(format!("未知参数 {arg}")),
+            }
+        }
+        Ok(args)
+    }
+
+    pub async fn run() -> Result<(), String> {
+        dotenv::dotenv().ok();
+        let Args { out, hook } = parse_args()?;
+        if hook && env::var_os("SITE_FETCH_ON_BUILD").is_none() {
+            println!("rusite-sitemap: SITE_FETCH_ON_BUILD is not set, skipped");
+            return Ok(());
+        }
+        let site_url = site_url();
+        let articles = get_articles_list().await.map_err(|e| e.to_string())?;
+
+        let files = [
+            (SITEMAP_FILE, render_sitemap(&collect_urls(&site_url, &articles))),
+            (ROBOTS_FILE, render_robots(&site_url)),
+        ];
+        fs::create_dir_all(&out).map_err(|e| format!("{}: {e}", out.display()))?;
+        for (name, content) in files {
+            let path = out.join(name);
+            fs::write(&path, content).map_err(|e| format!("{}: {e}", path.display()))?;
+            println!("wrote {}", path.display());
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use leptos::prelude::*;
+use leptos::{prelude::*, task::spawn_local};
 
-use crate::{components::ui::button::Link, state::use_app};
+use crate::{components::ui::button::{Button, Link}, state::{use_app, use_auth}};
 use leptos_icons::Icon;
 use icondata as i;
 
@@ -37,6 +37,7 @@ fn RouterLinks() -> impl IntoView {
         <div id="links" class="space-x-8">
             <Link href="/".to_string()>"Home"</Link>
             <Link href="/blog".to_string()>"Blog"</Link>
+            <Link href="/archive".to_string()>"Archive"</Link>
             <Link href="/about".to_string()>"About"</Link>
         </div>
     }
@@ -47,6 +48,9 @@ fn SearchBox() -> impl IntoView {
     let ass = use_app();
     view! {
         <button
+            aria-label="搜索"
+            aria-keyshortcuts="/ Control+K"
+            title="搜索（/ 或 Ctrl+K）"
             on:click=move |_| {
                 ass.active.set(true);
             }
@@ -59,7 +63,26 @@ fn SearchBox() -> impl IntoView {
 
 #[component]
 fn UserBox() -> impl IntoView {
+    let auth = use_auth();
     view! {
-        <b>"User"</b>
+        <Show when=move || auth.is_logged_in()
+            fallback=|| view! { <Link href="/login".to_string()>"Login"</Link> }
+        >
+            <div class="flex items-center space-x-4">
+                <Link href="/user".to_string()>
+                    <b>{move || auth.user.get().map(|user| user.username).unwrap_or_default()}</b>
+                </Link>
+                <Button
+                    class="text-gray-500 hover:text-blue-500"
+                    on_click=Callback::new(move |_| {
+                        spawn_local(async move {
+                            auth.logout().await;
+                        });
+                    })
+                >
+                    "Logout"
+                </Button>
+            </div>
+        </Show>
     }
 }
\ No newline at end of file
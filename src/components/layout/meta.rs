@@ -0,0 +1,68 @@
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Script, Title};
+
+use crate::{
+    models::{blog::ArticleInfo, markdown::FrontMatter},
+    seo::{absolute_url, article_path, blog_posting, json_ld, SITE_DESCRIPTION, SITE_NAME},
+};
+
+/// 页面标题、描述、规范链接以及 Open Graph / Twitter 卡片
+#[component]
+pub fn PageMeta(
+    #[prop(into)]
+    title: String,
+    /// 规范链接的路径，不含查询参数
+    #[prop(into)]
+    path: String,
+    #[prop(optional, into)]
+    description: Option<String>,
+    /// Open Graph 类型，默认为 `website`
+    #[prop(optional)]
+    og_type: Option<&'static str>,
+) -> impl IntoView {
+    let description = description.filter(|d| !d.is_empty()).unwrap_or_else(|| SITE_DESCRIPTION.to_string());
+    let url = absolute_url(&path);
+    view! {
+        <Title text=title.clone() />
+        <Meta name="description" content=description.clone() />
+        <Link rel="canonical" href=url.clone() />
+        <Meta property="og:site_name" content=SITE_NAME />
+        <Meta property="og:type" content=og_type.unwrap_or("website") />
+        <Meta property="og:title" content=title.clone() />
+        <Meta property="og:description" content=description.clone() />
+        <Meta property="og:url" content=url />
+        <Meta name="twitter:card" content="summary" />
+        <Meta name="twitter:title" content=title />
+        <Meta name="twitter:description" content=description />
+    }
+}
+
+/// 不希望被搜索引擎收录的页面，例如后台和登录页
+#[component]
+pub fn PrivatePageMeta(
+    #[prop(into)]
+    title: String,
+) -> impl IntoView {
+    view! {
+        <Title text=title />
+        <Meta name="robots" content="noindex" />
+    }
+}
+
+/// 文章页的元数据和 `BlogPosting` 结构化数据
+#[component]
+pub fn ArticleMeta(
+    info: ArticleInfo,
+    front_matter: Option<FrontMatter>,
+) -> impl IntoView {
+    let path = article_path(&info.aid);
+    let posting = blog_posting(&info, front_matter.as_ref(), &absolute_url(&path));
+    let title = posting["headline"].as_str().unwrap_or(&info.title).to_string();
+    view! {
+        <PageMeta title path description=info.summary.clone() og_type="article" />
+        <Meta property="article:published_time" content=info.created_at.to_rfc3339() />
+        <Meta property="article:modified_time" content=info.updated_at.to_rfc3339() />
+        {info.tags.iter().map(|tag| view! { <Meta property="article:tag" content=tag.clone() /> }).collect_view()}
+        <Script type_="application/ld+json">{json_ld(&posting)}</Script>
+    }
+}
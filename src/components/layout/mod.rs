@@ -1,3 +1,4 @@
 pub mod footer;
 pub mod header;
-pub mod backdrop;
\ No newline at end of file
+pub mod backdrop;
+pub mod meta;
\ No newline at end of file
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use leptos::prelude::*;
 use leptos_icons::Icon;
 use icondata as i;
-use crate::{components::ui::{button::{Button, Link}, icon::DividingLine}, models::blog::{Article, ArticleInfo, Category, Tag}, state::{remove_category, remove_tag, switch_category_selected, switch_tag_selected}, utils::*};
+use crate::{components::{layout::meta::ArticleMeta, ui::{button::{Button, Link}, comment::CommentSection, icon::DividingLine, toc::TableOfContents}}, models::{archive::YearMonth, blog::{Article, ArticleInfo, Category, SortOrder, Tag}}, search::query::FilterMode, state::{remove_category, remove_tag, switch_category_selected, switch_tag_selected}, utils::*};
 
 #[component]
 fn Card(
@@ -22,14 +22,14 @@ fn Card(
     /// 圆角大小
     #[prop(optional, default = CardRadius::Md)]
     radius: CardRadius,
-    /// 
+    /// 额外的类名
     #[prop(optional, into)]
     class: String,
     /// 主体内容
     children: Children,
 ) -> impl IntoView {
-    // 获取变体类名
-    let variant_class = variant.as_class();
+    // 获取变体类名（尚未接入类名组合）
+    let _variant_class = variant.as_class();
     // 阴影类名
     let elevation_class = elevation.as_class();
     let h_elevation_class = h_elevation.as_class();
@@ -230,9 +230,20 @@ pub fn ArticleCard(
     let updated_at = article.get().unwrap().updated_at();
     let tags = article.get().unwrap().tags().join(" | ");
     let categories = article.get().unwrap().categories().join(" | ");
+    let aid = article.get().unwrap().aid();
+    let info = article.with(|a| a.as_ref().unwrap().info().clone());
+
+    let toc = hmt.toc.clone();
 
     view! {
-        <Card class="flex mx-auto w-2/3 h-screen">
+        <ArticleMeta info front_matter=hmt.front_matter.clone() />
+        <div class="flex flex-col md:flex-row gap-8 justify-center items-start">
+        {(!toc.is_empty()).then(|| view! {
+            <aside class="w-full md:w-64 md:shrink-0 md:sticky md:top-24">
+                <TableOfContents toc=toc />
+            </aside>
+        })}
+        <Card class="flex w-full md:w-2/3 min-h-screen">
             <div class="flex flex-col gap-4 w-full">
                 <div id="header" class="flex flex-col gap-4">
                     <div class="flex flex-row items-center text-2xl gap-8">
@@ -263,8 +274,11 @@ pub fn ArticleCard(
                 <DividingLine/>
 
                 <div inner_html={hmt.html_content}></div>
+                <DividingLine/>
+                <CommentSection aid=aid />
             </div>
         </Card>
+        </div>
     }
 }
 
@@ -273,11 +287,48 @@ pub fn FilterBarCard(
     selected_tags: RwSignal<HashSet<String>>,
     selected_categories: RwSignal<HashSet<String>>,
     tags: RwSignal<Vec<Tag>>,
-    categories: RwSignal<Vec<Category>>
+    categories: RwSignal<Vec<Category>>,
+    filter_mode: RwSignal<FilterMode>,
+    sort_order: RwSignal<SortOrder>,
+    selected_month: RwSignal<Option<YearMonth>>,
+    /// 当前筛选条件下的文章数
+    #[prop(into)]
+    result_count: Signal<usize>,
 ) -> impl IntoView {
+    let option_class = move |active: bool| if active { "font-bold text-blue-500" } else { "text-gray-500" }.to_string();
     view! {
         <Card>
+            <div class="flex flex-wrap items-center gap-4 mb-4">
+                <span>{move || format!("共 {} 篇", result_count.get())}</span>
+                <div class="flex gap-2" role="group" aria-label="多个标签或分类的组合方式">
+                    {[FilterMode::Any, FilterMode::All].into_iter().map(|mode| view! {
+                        <Button
+                            class=Signal::derive(move || option_class(filter_mode.get() == mode))
+                            on_click=Callback::new(move |_| filter_mode.set(mode))
+                        >
+                            {mode.label()}
+                        </Button>
+                    }).collect_view()}
+                </div>
+                <div class="flex gap-2" role="group" aria-label="排序">
+                    {SortOrder::ALL.into_iter().map(|order| view! {
+                        <Button
+                            class=Signal::derive(move || option_class(sort_order.get() == order))
+                            on_click=Callback::new(move |_| sort_order.set(order))
+                        >
+                            {order.label()}
+                        </Button>
+                    }).collect_view()}
+                </div>
+            </div>
             <p>Select:</p>
+            {move || selected_month.get().map(|month| view! {
+                <div class="mb-4">
+                    <Button on_click=Callback::new(move |_| selected_month.set(None))>
+                        {month.to_string()}
+                    </Button>
+                </div>
+            })}
             // 显示已选择的tags
             <Show when=move || !selected_tags.get().is_empty()>
                 <div class="mb-4">
@@ -330,7 +381,7 @@ pub fn FilterBarCard(
                     each=move || tags.get()
                     key=move |tag| tag.name.clone()
                     children=move |tag| {
-                        let tag_name = tag.name.clone();
+                        let tag_name = format!("{} ({})", tag.name, tag.count);
                         view! {
                             <Button
                                 on_click=Callback::new(move |_| {
@@ -349,7 +400,7 @@ pub fn FilterBarCard(
                     each=move || categories.get()
                     key=move |category| category.name.clone()
                     children=move |category| {
-                        let category_name = category.name.clone();
+                        let category_name = format!("{} ({})", category.name, category.count);
                         view! {
                                 <Button
                                     on_click=Callback::new(move |_| {
@@ -0,0 +1,378 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use leptos::{ev, prelude::*, task::spawn_local};
+
+use crate::{
+    api::blog::{delete_comment, modify_comment, post_comment},
+    components::ui::{button::{Button, Link}, form::{FormError, SubmitButton}},
+    models::blog::{build_comment_tree, Comment, CommentCreateRequest, CommentModifyRequest, CommentNode},
+    state::{use_app, use_auth, AppState},
+    utils::{format_date_cn, render_comment_html},
+};
+
+/// 尚未被后端确认的评论使用的临时 id 前缀
+const PENDING_PREFIX: &str = "pending-";
+
+fn is_pending(comment: &Comment) -> bool {
+    comment.cid.starts_with(PENDING_PREFIX)
+}
+
+/// 最大嵌套层数，更深的回复会被展平
+const MAX_COMMENT_DEPTH: usize = 4;
+
+/// 评论区内共享的状态
+#[derive(Clone)]
+struct Thread {
+    aid: String,
+    error: RwSignal<Option<String>>,
+    /// 已折叠回复的评论
+    collapsed: RwSignal<HashSet<String>>,
+    /// 正在回复的评论
+    replying_to: RwSignal<Option<String>>,
+}
+
+#[component]
+pub fn CommentSection(
+    aid: String,
+) -> impl IntoView {
+    let state = use_app();
+    let auth = use_auth();
+    let article = state.get_article(aid.clone());
+    let comments = Memo::new(move |_| {
+        article.with(|a| a.as_ref().map(|a| a.comments().to_vec()).unwrap_or_default())
+    });
+    let tree = Memo::new(move |_| comments.with(|comments| build_comment_tree(comments, MAX_COMMENT_DEPTH)));
+    let thread = Thread {
+        aid: aid.clone(),
+        error: RwSignal::new(None),
+        collapsed: RwSignal::new(HashSet::new()),
+        replying_to: RwSignal::new(None),
+    };
+    let error = thread.error;
+    let composer_aid = aid.clone();
+
+    view! {
+        <section class="flex flex-col gap-4" aria-label="评论">
+            <h2 class="text-xl font-bold">
+                {move || format!("评论（{}）", comments.with(Vec::len))}
+            </h2>
+            <FormError error=error />
+            <ul class="flex flex-col gap-4">
+                <For
+                    each=move || tree.get()
+                    key=|node| node.comment.cid.clone()
+                    children=move |node| {
+                        view! { <CommentItem node=track_node(tree, node) thread=thread.clone() /> }
+                    }
+                />
+            </ul>
+            <Show when=move || auth.is_logged_in()
+                fallback=|| view! {
+                    <p class="text-sm text-gray-500">
+                        <Link href="/login".to_string() class="text-blue-500 hover:underline">"登录"</Link>
+                        "后参与评论"
+                    </p>
+                }
+            >
+                <CommentComposer aid=composer_aid.clone() error=error />
+            </Show>
+        </section>
+    }
+}
+
+#[component]
+fn CommentItem(
+    /// 随评论树更新的节点，编辑内容或新增回复时无需重建整个列表项
+    node: Memo<CommentNode>,
+    thread: Thread,
+) -> impl IntoView {
+    let comment = node.with_untracked(|node| node.comment.clone());
+    let Thread { aid, error, collapsed, replying_to } = thread.clone();
+    let state = use_app();
+    let auth = use_auth();
+    let editing = RwSignal::new(false);
+    let draft = RwSignal::new(String::new());
+    let pending = is_pending(&comment);
+    let is_own = {
+        let uid = comment.uid.clone();
+        move || !pending && auth.user.with(|user| user.as_ref().is_some_and(|user| user.uid == uid))
+    };
+
+    let start_editing = move |_| {
+        draft.set(node.with_untracked(|node| node.comment.content.clone()));
+        editing.set(true);
+    };
+
+    let on_save = {
+        let (state, aid, cid) = (state.clone(), aid.clone(), comment.cid.clone());
+        move |_| {
+            let content = draft.get_untracked().trim().to_string();
+            let original = node.with_untracked(|node| node.comment.content.clone());
+            editing.set(false);
+            if content.is_empty() || content == original {
+                return;
+            }
+            set_comment_content(&state, &aid, &cid, &content);
+            let (state, aid, cid) = (state.clone(), aid.clone(), cid.clone());
+            spawn_local(async move {
+                let request = CommentModifyRequest { cid: cid.clone(), content };
+                if let Err(err) = modify_comment(&aid, &request).await {
+                    set_comment_content(&state, &aid, &cid, &original);
+                    error.set(Some(format!("修改评论失败：{err}")));
+                }
+            });
+        }
+    };
+
+    let on_delete = {
+        let (state, aid, cid) = (state.clone(), aid.clone(), comment.cid.clone());
+        move |_| {
+            let mut removed = None;
+            state.update_comments(&aid, |comments| {
+                if let Some(idx) = comments.iter().position(|c| c.cid == cid) {
+                    removed = Some((idx, comments.remove(idx)));
+                }
+            });
+            let (state, aid, cid) = (state.clone(), aid.clone(), cid.clone());
+            spawn_local(async move {
+                if let Err(err) = delete_comment(&aid, &cid).await {
+                    if let Some((idx, comment)) = removed {
+                        state.update_comments(&aid, |comments| {
+                            comments.insert(idx.min(comments.len()), comment);
+                        });
+                    }
+                    error.set(Some(format!("删除评论失败：{err}")));
+                }
+            });
+        }
+    };
+
+    let content_html = Memo::new(move |_| node.with(|node| render_comment_html(&node.comment.content)));
+    let edited = move || node.with(|node| node.comment.updated_at > node.comment.created_at);
+    let reply_to = move || {
+        node.with(|node| node.reply_to.clone())
+            .map(|name| view! { <span>{format!("回复 @{name}")}</span> })
+    };
+    let cid = comment.cid.clone();
+    let replies = Memo::new(move |_| node.with(|node| node.replies.clone()));
+    let reply_count = Memo::new(move |_| replies.with(|replies| count_replies(replies)));
+    let has_replies = move || reply_count.get() > 0;
+    let is_collapsed = {
+        let cid = cid.clone();
+        Memo::new(move |_| collapsed.with(|collapsed| collapsed.contains(&cid)))
+    };
+    let toggle_collapsed = {
+        let cid = cid.clone();
+        move |_| collapsed.update(|collapsed| {
+            if !collapsed.remove(&cid) {
+                collapsed.insert(cid.clone());
+            }
+        })
+    };
+    let is_replying = {
+        let cid = cid.clone();
+        move || replying_to.with(|target| target.as_deref() == Some(cid.as_str()))
+    };
+    let toggle_reply = {
+        let cid = cid.clone();
+        move |_| replying_to.update(|target| {
+            *target = if target.as_deref() == Some(cid.as_str()) { None } else { Some(cid.clone()) };
+        })
+    };
+    let replies_id = format!("replies-{cid}");
+    let expanded_attr = move || (!is_collapsed.get()).to_string();
+    let toggle_label = move || {
+        if is_collapsed.get() { format!("展开 {} 条回复", reply_count.get()) } else { "收起回复".to_string() }
+    };
+    let can_reply = move || !pending && auth.is_logged_in();
+    let reply_parent = cid.clone();
+
+    view! {
+        <li class="flex flex-col gap-2 border-b border-gray-100 pb-4" class:opacity-60=pending>
+            <div class="flex flex-row items-center gap-4 text-sm text-gray-500">
+                <b class="text-gray-700">{comment.author().to_string()}</b>
+                {reply_to}
+                <span>{format_date_cn(comment.created_at)}</span>
+                <Show when=edited>
+                    <span>"（已编辑）"</span>
+                </Show>
+                <div class="flex gap-2 ml-auto">
+                    <Show when=can_reply>
+                        <Button class="hover:text-blue-500" on_click=Callback::new(toggle_reply.clone())>"回复"</Button>
+                    </Show>
+                    <Show when=is_own.clone()>
+                        <Button class="hover:text-blue-500" on_click=Callback::new(start_editing)>"编辑"</Button>
+                        <Button class="hover:text-red-500" on_click=Callback::new(on_delete.clone())>"删除"</Button>
+                    </Show>
+                </div>
+            </div>
+            <Show when=move || editing.get()
+                fallback=move || view! { <div class="text-gray-800" inner_html=content_html></div> }
+            >
+                <textarea
+                    class="w-full rounded-md border border-gray-300 px-3 py-2 text-sm"
+                    aria-label="编辑评论"
+                    prop:value=move || draft.get()
+                    on:input=move |ev| draft.set(event_target_value(&ev))
+                ></textarea>
+                <div class="flex gap-2 text-sm">
+                    <Button class="text-blue-500 hover:text-blue-700" on_click=Callback::new(on_save.clone())>"保存"</Button>
+                    <Button class="text-gray-500 hover:text-gray-700" on_click=Callback::new(move |_| editing.set(false))>"取消"</Button>
+                </div>
+            </Show>
+            <Show when=is_replying.clone()>
+                <CommentComposer
+                    aid=aid.clone()
+                    error=error
+                    parent=reply_parent.clone()
+                    on_done=Callback::new(move |_| replying_to.set(None))
+                />
+            </Show>
+            <Show when=has_replies>
+                <Button
+                    class="self-start text-xs text-blue-500 hover:text-blue-700"
+                    on_click=Callback::new(toggle_collapsed.clone())
+                    attr:aria-expanded=expanded_attr
+                    attr:aria-controls=replies_id.clone()
+                >
+                    {toggle_label}
+                </Button>
+            </Show>
+            <ul
+                id=format!("replies-{cid}")
+                role="group"
+                class="flex flex-col gap-4 ml-6 pl-4 border-l border-gray-200"
+                class:hidden=is_collapsed
+            >
+                <For
+                    each=move || replies.get()
+                    key=|child| child.comment.cid.clone()
+                    children=move |child| {
+                        view! { <CommentItem node=track_node(replies, child) thread=thread.clone() /> }.into_any()
+                    }
+                />
+            </ul>
+        </li>
+    }
+}
+
+/// 在 `nodes` 中按 `cid` 跟踪一个评论节点。节点被删除或移到别处后保留最后的值，
+/// 直到 `For` 销毁对应的视图
+fn track_node(nodes: Memo<Vec<CommentNode>>, initial: CommentNode) -> Memo<CommentNode> {
+    let cid = initial.comment.cid.clone();
+    Memo::new(move |previous: Option<&CommentNode>| {
+        nodes
+            .with(|nodes| nodes.iter().find(|node| node.comment.cid == cid).cloned())
+            .or_else(|| previous.cloned())
+            .unwrap_or_else(|| initial.clone())
+    })
+}
+
+fn count_replies(replies: &[CommentNode]) -> usize {
+    replies.iter().map(|reply| 1 + count_replies(&reply.replies)).sum()
+}
+
+#[component]
+fn CommentComposer(
+    aid: String,
+    error: RwSignal<Option<String>>,
+    /// 回复的评论 id，为空时发表顶层评论
+    #[prop(optional, into)]
+    parent: Option<String>,
+    /// 发表成功后的回调，用于关闭回复框
+    #[prop(optional)]
+    on_done: Option<Callback<()>>,
+) -> impl IntoView {
+    let state = use_app();
+    let auth = use_auth();
+    let draft = RwSignal::new(String::new());
+    let pending = RwSignal::new(false);
+    let is_reply = parent.is_some();
+    let input_id = match &parent {
+        Some(parent) => format!("comment-reply-{parent}"),
+        None => "comment-composer".to_string(),
+    };
+
+    let on_submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let content = draft.get_untracked().trim().to_string();
+        let Some(user) = auth.user.get_untracked() else {
+            return;
+        };
+        if content.is_empty() || pending.get_untracked() {
+            return;
+        }
+        // 先把评论插入列表，后端确认后替换为正式数据
+        let now = Utc::now();
+        let temp_cid = format!("{PENDING_PREFIX}{}", now.timestamp_millis());
+        let temp = Comment {
+            cid: temp_cid.clone(),
+            uid: user.uid.clone(),
+            parent: parent.clone(),
+            username: Some(user.username.clone()),
+            content: content.clone(),
+            created_at: now,
+            updated_at: now,
+        };
+        state.update_comments(&aid, |comments| comments.push(temp));
+        draft.set(String::new());
+        error.set(None);
+        pending.set(true);
+
+        let request = CommentCreateRequest {
+            aid: aid.clone(),
+            uid: user.uid,
+            content: content.clone(),
+            parent: parent.clone(),
+        };
+        let (state, aid) = (state.clone(), aid.clone());
+        spawn_local(async move {
+            match post_comment(&request).await {
+                Ok(saved) => {
+                    state.update_comments(&aid, |comments| {
+                        if let Some(slot) = comments.iter_mut().find(|c| c.cid == temp_cid) {
+                            *slot = saved;
+                        }
+                    });
+                    pending.set(false);
+                    // 发表成功后才关闭回复框，失败时草稿还要留在输入框里
+                    if let Some(on_done) = on_done {
+                        on_done.run(());
+                    }
+                }
+                Err(err) => {
+                    state.update_comments(&aid, |comments| comments.retain(|c| c.cid != temp_cid));
+                    draft.set(content);
+                    error.set(Some(format!("发表评论失败：{err}")));
+                    pending.set(false);
+                }
+            }
+        });
+    };
+
+    view! {
+        <form class="flex flex-col gap-2" on:submit=on_submit>
+            <label for=input_id.clone() class="text-sm text-gray-700">{if is_reply { "回复评论" } else { "发表评论" }}</label>
+            <textarea
+                id=input_id
+                class="w-full h-24 rounded-md border border-gray-300 px-3 py-2 text-sm focus:outline-none focus:ring focus:ring-blue-200"
+                placeholder="写下你的想法..."
+                prop:value=move || draft.get()
+                on:input=move |ev| draft.set(event_target_value(&ev))
+            ></textarea>
+            <div class="w-32 self-end">
+                <SubmitButton pending=pending>"发表"</SubmitButton>
+            </div>
+        </form>
+    }
+}
+
+fn set_comment_content(state: &AppState, aid: &str, cid: &str, content: &str) {
+    state.update_comments(aid, |comments| {
+        if let Some(comment) = comments.iter_mut().find(|c| c.cid == cid) {
+            comment.content = content.to_string();
+            comment.updated_at = Utc::now();
+        }
+    });
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+
+use crate::api::error::ApiError;
+
+/// 输入框类型
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum InputKind {
+    #[default]
+    Text,
+    Password,
+    Email,
+}
+
+impl InputKind {
+    fn as_type(&self) -> &'static str {
+        match self {
+            InputKind::Text => "text",
+            InputKind::Password => "password",
+            InputKind::Email => "email",
+        }
+    }
+}
+
+/// 按字段名记录的校验错误
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldErrors(HashMap<String, String>);
+
+impl FieldErrors {
+    pub fn set(&mut self, field: &str, message: impl Into<String>) {
+        self.0.insert(field.to_string(), message.into());
+    }
+    pub fn get(&self, field: &str) -> Option<String> {
+        self.0.get(field).cloned()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// 记录一次校验结果，返回校验是否通过
+    pub fn check(&mut self, field: &str, result: Result<(), String>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(message) => {
+                self.set(field, message);
+                false
+            }
+        }
+    }
+    /// 将后端的 `ErrorResponse` 映射到字段：错误码在 `fields`（错误码, 字段名）中时
+    /// 作为该字段的错误，否则作为表单级错误返回
+    pub fn from_api_error(error: &ApiError, fields: &[(u16, &str)]) -> (Self, Option<String>) {
+        let message = match error {
+            ApiError::Http { code, message, .. } => {
+                if let Some((_, field)) = fields.iter().find(|(field_code, _)| field_code == code) {
+                    let mut errors = Self::default();
+                    errors.set(field, message.clone());
+                    return (errors, None);
+                }
+                message.clone()
+            }
+            ApiError::Unauthorized(message) => message.clone(),
+            _ => format!("{}：{}", error.title(), error),
+        };
+        (Self::default(), Some(message))
+    }
+}
+
+pub fn validate_required(value: &str, label: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        Err(format!("请输入{label}"))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_length(value: &str, label: &str, min: usize, max: usize) -> Result<(), String> {
+    validate_required(value, label)?;
+    let len = value.chars().count();
+    if len < min || len > max {
+        Err(format!("{label}长度应为 {min}-{max} 个字符"))
+    } else {
+        Ok(())
+    }
+}
+
+/// 邮箱为可选项，填写时才校验格式
+pub fn validate_email(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    let valid = value
+        .split_once('@')
+        .is_some_and(|(local, domain)| {
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.') && !domain.ends_with('.')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err("邮箱格式不正确".to_string())
+    }
+}
+
+#[component]
+pub fn FormField(
+    /// 字段名，同时作为 input 的 id 和 name
+    name: &'static str,
+    label: &'static str,
+    value: RwSignal<String>,
+    #[prop(optional)]
+    kind: InputKind,
+    #[prop(optional, into)]
+    error: Signal<Option<String>>,
+    #[prop(optional, into)]
+    placeholder: String,
+    #[prop(optional, into)]
+    autocomplete: String,
+) -> impl IntoView {
+    let input_class = move || {
+        let border = if error.with(Option::is_some) { "border-red-400" } else { "border-gray-300" };
+        format!("w-full rounded-md border {border} px-3 py-2 text-gray-800 focus:outline-none focus:ring focus:ring-blue-200")
+    };
+    let error_id = format!("{name}-error");
+    view! {
+        <div class="flex flex-col gap-1">
+            <label for=name class="text-sm text-gray-700">{label}</label>
+            <input
+                id=name
+                name=name
+                type=kind.as_type()
+                class=input_class
+                placeholder=placeholder
+                autocomplete=autocomplete
+                aria-invalid=move || error.with(Option::is_some).to_string()
+                aria-describedby=error_id.clone()
+                bind:value=value
+            />
+            <FieldError id=error_id error=error />
+        </div>
+    }
+}
+
+#[component]
+pub fn TextAreaField(
+    name: &'static str,
+    label: &'static str,
+    value: RwSignal<String>,
+    #[prop(optional, into)]
+    error: Signal<Option<String>>,
+    #[prop(optional, into)]
+    placeholder: String,
+    /// 额外的类名，用于控制高度等
+    #[prop(optional, into)]
+    class: String,
+) -> impl IntoView {
+    let textarea_class = move || {
+        let border = if error.with(Option::is_some) { "border-red-400" } else { "border-gray-300" };
+        format!("w-full rounded-md border {border} px-3 py-2 text-gray-800 font-mono text-sm focus:outline-none focus:ring focus:ring-blue-200 {class}")
+    };
+    let error_id = format!("{name}-error");
+    view! {
+        <div class="flex flex-col gap-1">
+            <label for=name class="text-sm text-gray-700">{label}</label>
+            <textarea
+                id=name
+                name=name
+                class=textarea_class
+                placeholder=placeholder
+                aria-invalid=move || error.with(Option::is_some).to_string()
+                aria-describedby=error_id.clone()
+                prop:value=move || value.get()
+                on:input=move |ev| value.set(event_target_value(&ev))
+            ></textarea>
+            <FieldError id=error_id error=error />
+        </div>
+    }
+}
+
+#[component]
+pub fn FieldError(
+    #[prop(optional, into)]
+    id: String,
+    #[prop(into)]
+    error: Signal<Option<String>>,
+) -> impl IntoView {
+    view! {
+        <p id=id class="text-xs text-red-500 min-h-4" role="alert">
+            {move || error.get().unwrap_or_default()}
+        </p>
+    }
+}
+
+/// 表单级错误（通常来自后端）
+#[component]
+pub fn FormError(
+    #[prop(into)]
+    error: Signal<Option<String>>,
+) -> impl IntoView {
+    view! {
+        <Show when=move || error.with(Option::is_some)>
+            <div class="rounded-md bg-red-50 border border-red-200 px-3 py-2 text-sm text-red-600" role="alert">
+                {move || error.get().unwrap_or_default()}
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+pub fn SubmitButton(
+    #[prop(into)]
+    pending: Signal<bool>,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <button
+            type="submit"
+            class="w-full rounded-md bg-blue-500 hover:bg-blue-700 disabled:bg-blue-300 text-white font-bold py-2"
+            disabled=move || pending.get()
+            aria-busy=move || pending.get().to_string()
+        >
+            {children()}
+        </button>
+    }
+}
@@ -3,3 +3,5 @@ pub mod form;
 pub mod button;
 pub mod panle;
 pub mod card;
+pub mod comment;
+pub mod toc;
@@ -1,13 +1,103 @@
-use leptos::prelude::*;
+use std::{cell::RefCell, rc::Rc};
 
-use crate::{components::ui::button::Link, state::use_app};
+use leptos::{ev, prelude::*};
+use wasm_bindgen::JsCast;
 
+use crate::{
+    components::ui::button::Link,
+    search::{recent, snippet::Segment, SearchResult},
+    state::use_app,
+};
+
+const LISTBOX_ID: &str = "search-options";
+
+fn option_id(index: usize) -> String {
+    format!("search-option-{index}")
+}
+
+/// 搜索面板中的一个选项：输入为空时显示最近的搜索，否则显示搜索结果
+#[derive(Debug, Clone, PartialEq)]
+enum PaletteOption {
+    Result(SearchResult),
+    Recent(String),
+}
+
+/// 焦点在输入框等可编辑元素中时，`/` 应当作为普通字符输入
+fn is_editable(target: Option<web_sys::EventTarget>) -> bool {
+    target
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") || element.is_content_editable()
+        })
+}
+
+fn click_option(index: usize) {
+    if let Some(element) = document()
+        .get_element_by_id(&option_id(index))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        element.click();
+    }
+}
+
+fn scroll_option_into_view(index: usize) {
+    if let Some(element) = document().get_element_by_id(&option_id(index)) {
+        let options = web_sys::ScrollIntoViewOptions::new();
+        options.set_block(web_sys::ScrollLogicalPosition::Nearest);
+        element.scroll_into_view_with_scroll_into_view_options(&options);
+    }
+}
 
 #[component]
 pub fn SearchPanle(
     show: RwSignal<bool>,
-) -> impl IntoView { 
+) -> impl IntoView {
     let state = use_app();
+    let search_query = state.search_query;
+    let search_results = state.search_results;
+    let selected = RwSignal::new(0usize);
+    let recent_searches = RwSignal::new(recent::load());
+
+    // 全文搜索需要正文，打开搜索面板时在后台预取尚未加载的文章
+    Effect::new({
+        let state = state.clone();
+        move |_| {
+            if show.get() && !state.loading.get() {
+                state.prefetch_details();
+            }
+        }
+    });
+
+    // 全局快捷键：`/` 或 Ctrl+K（macOS 上为 ⌘K）打开搜索
+    let handle = window_event_listener(ev::keydown, move |ev| {
+        let command_k = ev.key().eq_ignore_ascii_case("k") && (ev.ctrl_key() || ev.meta_key());
+        let slash = ev.key() == "/" && !show.get_untracked() && !is_editable(ev.target());
+        if command_k {
+            ev.prevent_default();
+            show.update(|show| *show = !*show);
+        } else if slash {
+            ev.prevent_default();
+            show.set(true);
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    // 打开时记住之前的焦点，关闭时还回去
+    let previous_focus: Rc<RefCell<Option<web_sys::HtmlElement>>> = Rc::default();
+    Effect::new(move |was_open: Option<bool>| {
+        let open = show.get();
+        if open && was_open != Some(true) {
+            *previous_focus.borrow_mut() = document()
+                .active_element()
+                .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+        } else if !open
+            && was_open == Some(true)
+            && let Some(element) = previous_focus.borrow_mut().take()
+        {
+            let _ = element.focus();
+        }
+        open
+    });
 
     let input_element: NodeRef<leptos::html::Input> = NodeRef::new();
     Effect::new(move |_| {
@@ -15,41 +105,143 @@ pub fn SearchPanle(
             let _ = input.focus();
         }
     });
-    
+
+    let options = Memo::new(move |_| {
+        if search_query.with(|query| query.trim().is_empty()) {
+            recent_searches.get().into_iter().map(PaletteOption::Recent).collect::<Vec<_>>()
+        } else {
+            search_results.get().into_iter().map(PaletteOption::Result).collect()
+        }
+    });
+    // 选项变化时回到第一项
+    Effect::new(move |_| {
+        options.track();
+        selected.set(0);
+    });
+    Effect::new(move |_| {
+        if show.get() && !options.with(Vec::is_empty) {
+            scroll_option_into_view(selected.get());
+        }
+    });
+
+    let on_keydown = move |ev: ev::KeyboardEvent| {
+        let count = options.with_untracked(Vec::len);
+        match ev.key().as_str() {
+            "Escape" => show.set(false),
+            "ArrowDown" if count > 0 => {
+                ev.prevent_default();
+                selected.update(|selected| *selected = (*selected + 1) % count);
+            }
+            "ArrowUp" if count > 0 => {
+                ev.prevent_default();
+                selected.update(|selected| *selected = (*selected + count - 1) % count);
+            }
+            "Enter" if count > 0 => {
+                ev.prevent_default();
+                click_option(selected.get_untracked());
+            }
+            // 面板中只有输入框可以获得焦点，把焦点留在面板内
+            "Tab" => ev.prevent_default(),
+            _ => {}
+        }
+    };
+
+    let on_open_result = move |_| {
+        recent_searches.set(recent::remember(&search_query.get_untracked()));
+        show.set(false);
+    };
+
     view! {
         <Show when=move || show.get()>
             <div
                 class="fixed inset-x-0 mx-auto z-100 top-1/6 max-w-2xl items-center justify-center bg-white min-h-1/2"
-                on:keydown=move|ev| {
-                    if show.get() && ev.key() == "Escape" {
-                        show.set(false);
-                    }
-                }
+                role="dialog"
+                aria-modal="true"
+                aria-label="搜索文章"
+                on:keydown=on_keydown
             >
                 <input
                     id="search"
                     class="w-full rounded-full bg-gray-100 px-4 py-2 text-gray-800 dark:bg-gray-800 dark:text-gray-200"
                     type="text"
+                    role="combobox"
+                    aria-autocomplete="list"
+                    aria-controls=LISTBOX_ID
+                    aria-expanded=move || (!options.with(Vec::is_empty)).to_string()
+                    aria-activedescendant=move || (!options.with(Vec::is_empty)).then(|| option_id(selected.get()))
                     autocomplete="off"
-                    placeholder="Search..."
-                    bind:value=state.search_query
+                    placeholder="Search... (tag:rust category:notes after:2025-01-01 \"phrase\" -exclude)"
+                    bind:value=search_query
                     node_ref=input_element
                 >
 
                 </input>
-                <For
-                    each=move || state.search_results.get()
-                    key=|article| article.aid().clone()
-                    children=move |article| {
-                        let url = format!("/blog/{}", article.aid());
-                        view! {
-                            <Link href=url on_click=Callback::new(move |_| {show.set(false);} )>
-                                <p> { article.title().to_string() } </p>
-                            </Link>
-                        }
-                    }
-                />
+                <Show when=move || state.parsed_query.with(Result::is_err)>
+                    <p class="px-4 py-2 text-sm text-red-500" role="alert">
+                        {move || state.parsed_query.with(|query| query.as_ref().err().map(ToString::to_string))}
+                    </p>
+                </Show>
+                <Show when=move || search_query.with(|query| query.trim().is_empty()) && !recent_searches.with(Vec::is_empty)>
+                    <p class="px-4 pt-2 text-xs text-gray-400">"最近搜索"</p>
+                </Show>
+                <ul id=LISTBOX_ID role="listbox" aria-label="搜索结果" class="max-h-[60vh] overflow-y-auto">
+                    {move || options.get().into_iter().enumerate().map(|(index, option)| {
+                        let is_selected = move || selected.get() == index;
+                        let item_class = move || if is_selected() { "block px-4 py-2 bg-blue-50" } else { "block px-4 py-2 hover:bg-gray-100" };
+                        let content = match option {
+                            PaletteOption::Result(result) => {
+                                let url = format!("/blog/{}", result.article.aid());
+                                view! {
+                                    <Link
+                                        href=url
+                                        on_click=Callback::new(on_open_result)
+                                        attr:id=option_id(index)
+                                        attr:role="option"
+                                        attr:tabindex="-1"
+                                        attr:aria-selected=move || is_selected().to_string()
+                                    >
+                                        <div class=item_class>
+                                            <p class="font-bold">{highlighted(result.title)}</p>
+                                            <p class="text-sm text-gray-500">{highlighted(result.snippet)}</p>
+                                        </div>
+                                    </Link>
+                                }.into_any()
+                            }
+                            PaletteOption::Recent(query) => {
+                                let label = query.clone();
+                                view! {
+                                    <button
+                                        type="button"
+                                        id=option_id(index)
+                                        role="option"
+                                        tabindex="-1"
+                                        aria-selected=move || is_selected().to_string()
+                                        class=move || format!("w-full text-left text-gray-700 {}", item_class())
+                                        on:click=move |_| search_query.set(query.clone())
+                                    >
+                                        {label}
+                                    </button>
+                                }.into_any()
+                            }
+                        };
+                        view! { <li role="presentation">{content}</li> }
+                    }).collect_view()}
+                </ul>
             </div>
         </Show>
     }
-}
\ No newline at end of file
+}
+
+/// 命中的检索词用 `<mark>` 标出
+fn highlighted(segments: Vec<Segment>) -> impl IntoView {
+    segments
+        .into_iter()
+        .map(|segment| {
+            if segment.highlighted {
+                view! { <mark class="bg-yellow-200">{segment.text}</mark> }.into_any()
+            } else {
+                segment.text.into_any()
+            }
+        })
+        .collect_view()
+}
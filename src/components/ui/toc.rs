@@ -0,0 +1,129 @@
+use leptos::{ev, prelude::*};
+use wasm_bindgen::JsValue;
+
+use crate::models::markdown::{build_toc_tree, Heading, TocNode};
+
+/// 标题距视口顶部小于该值时视为“当前阅读位置”（需要避开吸顶的导航栏）
+const SCROLL_SPY_OFFSET: f64 = 120.0;
+
+fn heading_top(id: &str) -> Option<f64> {
+    let element = web_sys::window()?.document()?.get_element_by_id(id)?;
+    Some(element.get_bounding_client_rect().top())
+}
+
+/// 找出最后一个已经滚过视口顶部的标题
+fn current_heading(ids: &[String]) -> Option<String> {
+    let mut active = None;
+    for id in ids {
+        match heading_top(id) {
+            Some(top) if top <= SCROLL_SPY_OFFSET => active = Some(id.clone()),
+            Some(_) => break,
+            None => {}
+        }
+    }
+    active.or_else(|| ids.first().cloned())
+}
+
+/// 平滑滚动到标题并更新地址栏中的锚点（不产生新的历史记录）
+fn scroll_to_heading(id: &str) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    if let Some(element) = window.document().and_then(|document| document.get_element_by_id(id)) {
+        let options = web_sys::ScrollIntoViewOptions::new();
+        options.set_behavior(web_sys::ScrollBehavior::Smooth);
+        element.scroll_into_view_with_scroll_into_view_options(&options);
+    }
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&format!("#{id}")));
+    }
+}
+
+#[component]
+pub fn TableOfContents(
+    toc: Vec<Heading>,
+) -> impl IntoView {
+    let ids = toc.iter().map(|heading| heading.id.clone()).collect::<Vec<_>>();
+    let tree = build_toc_tree(&toc);
+    let active = RwSignal::new(None::<String>);
+    // 窄屏下默认折叠，宽屏下 CSS 会忽略该状态
+    let collapsed = RwSignal::new(true);
+
+    let handle = window_event_listener(ev::scroll, {
+        let ids = ids.clone();
+        move |_| active.set(current_heading(&ids))
+    });
+    on_cleanup(move || handle.remove());
+
+    // 打开带锚点的链接时滚动到对应标题
+    Effect::new(move |_| {
+        let hash = web_sys::window().and_then(|window| window.location().hash().ok()).unwrap_or_default();
+        let id = hash.trim_start_matches('#');
+        if ids.iter().any(|candidate| candidate == id) {
+            scroll_to_heading(id);
+            active.set(Some(id.to_string()));
+        } else {
+            active.set(current_heading(&ids));
+        }
+    });
+
+    view! {
+        <nav class="toc-container" style="top: 6rem" aria-label="目录">
+            <div class="toc-header">
+                <span class="toc-title">"目录"</span>
+                <button
+                    class="toc-toggle"
+                    aria-controls="toc-list"
+                    aria-expanded=move || (!collapsed.get()).to_string()
+                    on:click=move |_| collapsed.update(|c| *c = !*c)
+                >
+                    {move || if collapsed.get() { "展开" } else { "收起" }}
+                </button>
+            </div>
+            <ul id="toc-list" class="toc-list" class:collapsed=move || collapsed.get()>
+                {tree.into_iter().map(|node| view! { <TocItem node=node active=active /> }).collect_view()}
+            </ul>
+        </nav>
+    }
+}
+
+#[component]
+fn TocItem(
+    node: TocNode,
+    active: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let TocNode { heading, children } = node;
+    let id = heading.id.clone();
+    let is_active = {
+        let id = id.clone();
+        move || active.with(|active| active.as_deref() == Some(id.as_str()))
+    };
+    let on_click = {
+        let id = id.clone();
+        move |ev: ev::MouseEvent| {
+            ev.prevent_default();
+            scroll_to_heading(&id);
+            active.set(Some(id.clone()));
+        }
+    };
+    let children = children
+        .into_iter()
+        .map(|child| view! { <TocItem node=child active=active /> }.into_any())
+        .collect::<Vec<_>>();
+    let has_children = !children.is_empty();
+
+    view! {
+        <li class="toc-item">
+            <a
+                href=format!("#{id}")
+                class="toc-link"
+                class:active=is_active.clone()
+                aria-current=move || is_active().then_some("location")
+                on:click=on_click
+            >
+                {heading.text}
+            </a>
+            {has_children.then(|| view! { <ul class="toc-list pl-2">{children}</ul> })}
+        </li>
+    }
+}
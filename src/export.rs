@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use pulldown_cmark::{Event, Parser, Tag as MarkdownTag, TagEnd};
+use serde::Serialize;
+
+use crate::{
+    api::blog::{comments_path, content_path, ARTICLE_LIST_PATH, CATEGORIES_PATH, SEARCH_INDEX_PATH, TAGS_PATH},
+    app::{PRIVATE_ROUTES, STATIC_ROUTES},
+    models::{
+        blog::{Article, ArticleInfo, Category, Comment, Tag},
+        response::ApiResponse,
+    },
+    search::index::SearchIndex,
+    seo::article_path,
+    state::{Preloaded, PreloadedDetails},
+    utils::parse_front_matter,
+};
+
+/// 只读接口的静态镜像所在目录，部署时 `API_BASE_URL` 应指向 `站点地址/api`
+pub const API_DIR: &str = "api";
+/// 静态托管找不到文件时返回的页面
+pub const NOT_FOUND_FILE: &str = "404.html";
+/// 从正文生成的摘要的最大字符数
+const SUMMARY_CHARS: usize = 120;
+
+/// 导出的一篇文章
+#[derive(Debug, Clone)]
+pub struct ExportedArticle {
+    pub info: ArticleInfo,
+    /// Markdown 正文，设置了访问密码的文章为 `None`
+    pub content: Option<String>,
+    pub comments: Vec<Comment>,
+}
+
+impl ExportedArticle {
+    /// 由带 Front Matter 的 Markdown 文件生成文章。
+    /// Front Matter 缺少标题时使用 `aid`，缺少日期时使用文件的修改时间，摘要取正文第一段
+    pub fn from_markdown(aid: &str, markdown: &str, modified: DateTime<Utc>) -> Self {
+        let (front_matter, body) = parse_front_matter(markdown);
+        let title = front_matter
+            .as_ref()
+            .map(|front_matter| front_matter.title.clone())
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| aid.to_string());
+        let created_at = front_matter
+            .as_ref()
+            .and_then(|front_matter| parse_date(&front_matter.date))
+            .unwrap_or(modified);
+        let (tags, categories) = front_matter
+            .map(|front_matter| (front_matter.tags, front_matter.categories))
+            .unwrap_or_default();
+        let info = ArticleInfo {
+            aid: aid.to_string(),
+            title,
+            summary: summarize(&body),
+            secret: None,
+            tags,
+            categories,
+            created_at,
+            updated_at: modified.max(created_at),
+        };
+        Self { info, content: Some(markdown.to_string()), comments: Vec::new() }
+    }
+}
+
+/// 导出站点所需的全部数据
+#[derive(Debug, Clone)]
+pub struct SiteData {
+    pub articles: Vec<ExportedArticle>,
+    pub tags: Vec<Tag>,
+    pub categories: Vec<Category>,
+}
+
+impl SiteData {
+    /// 由文章统计标签和分类，用于没有后端的 Markdown 目录
+    pub fn new(articles: Vec<ExportedArticle>) -> Self {
+        let mut tags = BTreeMap::<String, i32>::new();
+        let mut categories = BTreeMap::<String, i32>::new();
+        for article in &articles {
+            for tag in &article.info.tags {
+                *tags.entry(tag.clone()).or_default() += 1;
+            }
+            for category in &article.info.categories {
+                *categories.entry(category.clone()).or_default() += 1;
+            }
+        }
+        Self {
+            articles,
+            tags: tags.into_iter().map(|(name, count)| Tag { name, count }).collect(),
+            categories: categories.into_iter().map(|(name, count)| Category { name, count }).collect(),
+        }
+    }
+
+    pub fn infos(&self) -> Vec<ArticleInfo> {
+        self.articles.iter().map(|article| article.info.clone()).collect()
+    }
+
+    /// 需要预渲染的路由：`app.rs` 中的页面和所有文章页
+    pub fn routes(&self) -> Vec<String> {
+        STATIC_ROUTES
+            .iter()
+            .chain(PRIVATE_ROUTES)
+            .map(|route| route.to_string())
+            .chain(self.articles.iter().map(|article| article_path(&article.info.aid)))
+            .collect()
+    }
+
+    /// 预渲染 `route` 时嵌入页面的数据，文章页另外带上正文和评论
+    pub fn preloaded(&self, route: &str) -> Preloaded {
+        let details = self
+            .articles
+            .iter()
+            .filter(|article| article_path(&article.info.aid) == route)
+            .filter_map(|article| {
+                Some(PreloadedDetails {
+                    aid: article.info.aid.clone(),
+                    content: article.content.clone()?,
+                    comments: article.comments.clone(),
+                })
+            })
+            .collect();
+        Preloaded {
+            articles: self.infos(),
+            tags: self.tags.clone(),
+            categories: self.categories.clone(),
+            details,
+            search_index: true,
+        }
+    }
+
+    /// 预先生成的全文索引，设置了访问密码的文章只索引摘要信息
+    pub fn search_index(&self) -> SearchIndex {
+        let articles = self
+            .articles
+            .iter()
+            .map(|article| match &article.content {
+                Some(content) => Article::new(article.info.clone(), content, article.comments.clone()),
+                None => Article::from_info(article.info.clone()),
+            })
+            .collect::<Vec<_>>();
+        let mut index = SearchIndex::new();
+        index.sync(&articles);
+        index
+    }
+
+    /// 只读接口的静态镜像，返回相对输出目录的文件路径和 `ApiResponse` 格式的内容。
+    /// 页面加载后浏览器仍通过这些接口刷新列表、打开其它文章以及下载全文搜索所需的索引
+    pub fn api_files(&self) -> Result<Vec<(String, String)>, serde_json::Error> {
+        let mut files = vec![
+            api_file(ARTICLE_LIST_PATH, &self.infos())?,
+            api_file(TAGS_PATH, &self.tags)?,
+            api_file(CATEGORIES_PATH, &self.categories)?,
+            api_file(SEARCH_INDEX_PATH, &self.search_index())?,
+        ];
+        for article in &self.articles {
+            if let Some(content) = &article.content {
+                files.push(api_file(&content_path(&article.info.aid), content)?);
+            }
+            files.push(api_file(&comments_path(&article.info.aid), &article.comments)?);
+        }
+        Ok(files)
+    }
+}
+
+/// 路由对应的 HTML 文件，静态托管访问目录时返回其中的 `index.html`
+pub fn page_file(route: &str) -> String {
+    match route.trim_matches('/') {
+        "" => "index.html".to_string(),
+        dir => format!("{dir}/index.html"),
+    }
+}
+
+fn api_file<T: Serialize>(path: &str, data: &T) -> Result<(String, String), serde_json::Error> {
+    let response = ApiResponse { code: 200, message: "success".to_string(), data };
+    Ok((format!("{API_DIR}{path}"), serde_json::to_string(&response)?))
+}
+
+/// 解析 Front Matter 中的日期，支持 RFC 3339、`YYYY-MM-DD HH:MM[:SS]` 和 `YYYY-MM-DD`，后两者按 UTC 处理
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.to_utc());
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(date, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+/// 取正文第一段的纯文本作为摘要
+fn summarize(markdown: &str) -> String {
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(MarkdownTag::Paragraph) => in_paragraph = true,
+            Event::End(TagEnd::Paragraph) if !text.is_empty() => break,
+            Event::End(TagEnd::Paragraph) => in_paragraph = false,
+            Event::Text(part) | Event::Code(part) if in_paragraph => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+    if text.chars().count() > SUMMARY_CHARS {
+        text.chars().take(SUMMARY_CHARS).chain(['…']).collect()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    const POST: &str = "---\ntitle: Hello <World>\ndate: 2025-01-02\ntags: [rust, wasm]\ncategories: [notes]\n---\n# Heading\n\nFirst `line`\nsecond line.\n\nMore text.\n";
+
+    fn modified() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_800_000_000, 0).unwrap()
+    }
+
+    fn site() -> SiteData {
+        let mut locked = ExportedArticle::from_markdown("locked", "plain body", modified());
+        locked.info.secret = Some("pw".to_string());
+        locked.content = None;
+        SiteData::new(vec![ExportedArticle::from_markdown("hello", POST, modified()), locked])
+    }
+
+    #[test]
+    fn reads_front_matter_and_summary() {
+        let article = ExportedArticle::from_markdown("hello", POST, modified());
+        assert_eq!(article.info.title, "Hello <World>");
+        assert_eq!(article.info.tags, ["rust", "wasm"]);
+        assert_eq!(article.info.categories, ["notes"]);
+        assert_eq!(article.info.summary, "First line second line.");
+        assert_eq!(article.info.created_at.to_rfc3339(), "2025-01-02T00:00:00+00:00");
+        assert_eq!(article.info.updated_at, modified());
+        assert_eq!(article.content.as_deref(), Some(POST));
+
+        let article = ExportedArticle::from_markdown("plain", &"字".repeat(200), modified());
+        assert_eq!(article.info.title, "plain");
+        assert_eq!(article.info.created_at, modified());
+        assert_eq!(article.info.summary.chars().count(), SUMMARY_CHARS + 1);
+    }
+
+    #[test]
+    fn parses_front_matter_dates() {
+        let expected = DateTime::from_timestamp(1_735_783_200, 0);
+        assert_eq!(parse_date("2025-01-02T02:00:00Z"), expected);
+        assert_eq!(parse_date("2025-01-02T10:00:00+08:00"), expected);
+        assert_eq!(parse_date("2025-01-02 02:00"), expected);
+        assert_eq!(parse_date("2025-01-02 02:00:00"), expected);
+        assert!(parse_date("yesterday").is_none());
+    }
+
+    #[test]
+    fn counts_terms_and_lists_routes() {
+        let site = site();
+        assert_eq!(site.tags.iter().map(|tag| (tag.name.as_str(), tag.count)).collect::<Vec<_>>(), [("rust", 1), ("wasm", 1)]);
+        let routes = site.routes();
+        assert!(routes.iter().any(|route| route == "/"));
+        assert!(routes.iter().any(|route| route == "/admin"));
+        assert!(routes.iter().any(|route| route == "/blog/locked"));
+        assert_eq!(page_file("/"), "index.html");
+        assert_eq!(page_file("/blog/hello"), "blog/hello/index.html");
+    }
+
+    #[test]
+    fn preloads_details_of_the_article_page_only() {
+        let site = site();
+        let preloaded = site.preloaded("/blog/hello");
+        assert_eq!(preloaded.articles.len(), 2);
+        assert_eq!(preloaded.details.len(), 1);
+        assert_eq!(preloaded.details[0].content, POST);
+        assert!(site.preloaded("/blog").details.is_empty());
+        assert!(site.preloaded("/blog/locked").details.is_empty());
+    }
+
+    #[test]
+    fn mirrors_read_only_api() {
+        let files = site().api_files().unwrap();
+        let paths = files.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, [
+            "api/article/list",
+            "api/article/tags",
+            "api/article/categories",
+            "api/search/index",
+            "api/article/hello/content",
+            "api/article/hello/comment",
+            "api/article/locked/comment",
+        ]);
+        let list: Value = serde_json::from_str(&files[0].1).unwrap();
+        assert_eq!(list["code"], 200);
+        assert_eq!(list["data"][0]["aid"], "hello");
+        let content: Value = serde_json::from_str(&files[4].1).unwrap();
+        assert_eq!(content["data"], POST);
+    }
+
+    #[test]
+    fn exports_search_index() {
+        let files = site().api_files().unwrap();
+        let response: ApiResponse<SearchIndex> = serde_json::from_str(&files[3].1).unwrap();
+        let index = response.data;
+        assert_eq!(index.len(), 2);
+        assert!(index.has_content(&site().articles[0].info));
+        assert!(!index.has_content(&site().articles[1].info));
+        let hits = index.search(&["second".to_string()], false, 10);
+        assert_eq!(hits.iter().map(|hit| hit.aid.as_str()).collect::<Vec<_>>(), ["hello"]);
+        assert!(site().preloaded("/").search_index);
+    }
+}
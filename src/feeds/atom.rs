@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+use chrono::SecondsFormat;
+
+use crate::feeds::{escape_xml, Feed, ATOM_FILE};
+
+/// 生成 Atom 1.0
+pub fn render(feed: &Feed) -> String {
+    let config = &feed.config;
+    let rfc3339 = |date: chrono::DateTime<chrono::Utc>| date.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    let _ = write!(xml, r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{}">"#, escape_xml(&config.language));
+    let _ = write!(
+        xml,
+        r#"<id>{home}</id><title>{}</title><subtitle>{}</subtitle><updated>{}</updated><link href="{home}"/><link href="{}" rel="self" type="application/atom+xml"/>"#,
+        escape_xml(&config.title),
+        escape_xml(&config.description),
+        rfc3339(feed.updated()),
+        escape_xml(&config.url(&format!("/{ATOM_FILE}"))),
+        home = escape_xml(&config.url("/")),
+    );
+    // Atom 要求每个条目或整个订阅源提供作者
+    let _ = write!(
+        xml,
+        "<author><name>{}</name></author>",
+        escape_xml(config.author.as_deref().unwrap_or(&config.title)),
+    );
+    for entry in &feed.entries {
+        xml.push_str("<entry>");
+        let _ = write!(
+            xml,
+            r#"<id>{url}</id><title>{}</title><link href="{url}"/><published>{}</published><updated>{}</updated><summary>{}</summary>"#,
+            escape_xml(&entry.title),
+            rfc3339(entry.published),
+            rfc3339(entry.updated),
+            escape_xml(&entry.summary),
+            url = escape_xml(&entry.url),
+        );
+        for category in &entry.categories {
+            let _ = write!(xml, r#"<category term="{}"/>"#, escape_xml(category));
+        }
+        if let Some(html) = &entry.content_html {
+            let _ = write!(xml, r#"<content type="html">{}</content>"#, escape_xml(html));
+        }
+        xml.push_str("</entry>");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::tests::{parse_xml, sample_feed, texts, Element};
+
+    fn attribute<'a>(elements: &'a [Element], path: &str, name: &str) -> Vec<&'a str> {
+        elements
+            .iter()
+            .filter(|element| element.path == path)
+            .filter_map(|element| element.attributes.get(name).map(String::as_str))
+            .collect()
+    }
+
+    #[test]
+    fn renders_required_atom_fields() {
+        let elements = parse_xml(&render(&sample_feed()));
+        assert_eq!(elements[0].path, "feed");
+        assert_eq!(elements[0].attributes["xmlns"], "http://www.w3.org/2005/Atom");
+
+        assert_eq!(texts(&elements, "feed/id"), ["https://example.com/"]);
+        assert_eq!(texts(&elements, "feed/title"), ["Rusite"]);
+        assert_eq!(texts(&elements, "feed/updated"), ["1970-01-03T01:00:00Z"]);
+        assert_eq!(texts(&elements, "feed/author/name"), ["Ryan"]);
+        assert_eq!(attribute(&elements, "feed/link", "href"), ["https://example.com/", "https://example.com/atom.xml"]);
+        assert_eq!(attribute(&elements, "feed/link", "rel"), ["self"]);
+
+        assert_eq!(texts(&elements, "feed/entry/id"), ["https://example.com/blog/b", "https://example.com/blog/a"]);
+        assert_eq!(texts(&elements, "feed/entry/title"), ["Locked", "Tom & Jerry"]);
+        assert_eq!(texts(&elements, "feed/entry/updated"), ["1970-01-03T01:00:00Z", "1970-01-02T01:00:00Z"]);
+        assert_eq!(texts(&elements, "feed/entry/published"), ["1970-01-03T00:00:00Z", "1970-01-02T00:00:00Z"]);
+        assert_eq!(attribute(&elements, "feed/entry/link", "href"), texts(&elements, "feed/entry/id"));
+        assert_eq!(texts(&elements, "feed/entry/summary"), ["Summary of Locked", "Summary of Tom & Jerry"]);
+        assert_eq!(attribute(&elements, "feed/entry/category", "term"), ["rust", "notes", "rust", "notes"]);
+        assert_eq!(attribute(&elements, "feed/entry/content", "type"), ["html"]);
+    }
+}
@@ -0,0 +1,75 @@
+use serde_json::{json, Map, Value};
+
+use crate::feeds::{Feed, JSON_FEED_FILE};
+
+const VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+/// 生成 JSON Feed 1.1
+pub fn render(feed: &Feed) -> String {
+    let config = &feed.config;
+    let items = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            let mut item = Map::new();
+            item.insert("id".into(), json!(entry.url));
+            item.insert("url".into(), json!(entry.url));
+            item.insert("title".into(), json!(entry.title));
+            item.insert("summary".into(), json!(entry.summary));
+            // content_html 和 content_text 至少要有一个
+            match &entry.content_html {
+                Some(html) => item.insert("content_html".into(), json!(html)),
+                None => item.insert("content_text".into(), json!(entry.summary)),
+            };
+            item.insert("date_published".into(), json!(entry.published.to_rfc3339()));
+            item.insert("date_modified".into(), json!(entry.updated.to_rfc3339()));
+            item.insert("tags".into(), json!(entry.categories));
+            Value::Object(item)
+        })
+        .collect::<Vec<_>>();
+
+    let mut document = json!({
+        "version": VERSION,
+        "title": config.title,
+        "home_page_url": config.url("/"),
+        "feed_url": config.url(&format!("/{JSON_FEED_FILE}")),
+        "description": config.description,
+        "language": config.language,
+        "items": items,
+    });
+    if let Some(author) = &config.author {
+        document["authors"] = json!([{ "name": author }]);
+    }
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::tests::sample_feed;
+
+    #[test]
+    fn renders_required_json_feed_fields() {
+        let document: Value = serde_json::from_str(&render(&sample_feed())).unwrap();
+        assert_eq!(document["version"], VERSION);
+        assert_eq!(document["title"], "Rusite");
+        assert_eq!(document["home_page_url"], "https://example.com/");
+        assert_eq!(document["feed_url"], "https://example.com/feed.json");
+        assert_eq!(document["description"], "A <Rust> blog");
+        assert_eq!(document["authors"], json!([{ "name": "Ryan" }]));
+        let items = document["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        for item in items {
+            assert_eq!(item["id"], item["url"]);
+            assert!(item["title"].is_string());
+            assert!(item["content_html"].is_string() != item["content_text"].is_string());
+        }
+        assert_eq!(items[0]["id"], "https://example.com/blog/b");
+        assert_eq!(items[0]["content_text"], "Summary of Locked");
+        assert_eq!(items[0]["date_published"], "1970-01-03T00:00:00+00:00");
+        assert_eq!(items[0]["date_modified"], "1970-01-03T01:00:00+00:00");
+        assert!(items[1]["content_html"].as_str().unwrap().contains("<h1"));
+        assert_eq!(items[1]["summary"], "Summary of Tom & Jerry");
+        assert_eq!(items[1]["tags"], json!(["rust", "notes"]));
+    }
+}
@@ -0,0 +1,231 @@
+pub mod atom;
+pub mod json;
+pub mod rss;
+
+use chrono::{DateTime, Utc};
+
+use crate::{models::blog::ArticleInfo, seo::{SITE_DESCRIPTION, SITE_NAME}, utils::parse_markdown};
+
+/// 每个订阅源最多包含的文章数
+pub const FEED_LIMIT: usize = 20;
+
+pub const RSS_FILE: &str = "rss.xml";
+pub const ATOM_FILE: &str = "atom.xml";
+pub const JSON_FEED_FILE: &str = "feed.json";
+
+/// 站点信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedConfig {
+    pub title: String,
+    pub description: String,
+    /// 站点根地址，不带末尾的 `/`
+    pub site_url: String,
+    pub author: Option<String>,
+    pub language: String,
+}
+
+impl FeedConfig {
+    pub fn new(title: &str, description: &str, site_url: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            description: description.to_string(),
+            site_url: site_url.trim_end_matches('/').to_string(),
+            author: None,
+            language: "zh-CN".to_string(),
+        }
+    }
+
+    /// 从环境变量读取站点信息：`SITE_TITLE`、`SITE_DESCRIPTION`、`SITE_URL`、`SITE_AUTHOR`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_env() -> Self {
+        let var = |key: &str, default: &str| std::env::var(key).unwrap_or_else(|_| default.to_string());
+        let mut config = Self::new(
+            &var("SITE_TITLE", SITE_NAME),
+            &var("SITE_DESCRIPTION", SITE_DESCRIPTION),
+            &crate::seo::site_url(),
+        );
+        config.author = std::env::var("SITE_AUTHOR").ok();
+        config
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.site_url, path)
+    }
+}
+
+/// 订阅源中的一篇文章
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+    /// 渲染后的正文，设置了访问密码或没有正文时为 `None`
+    pub content_html: Option<String>,
+    /// 标签和分类
+    pub categories: Vec<String>,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl FeedEntry {
+    pub fn new(config: &FeedConfig, info: &ArticleInfo, markdown: Option<&str>) -> Self {
+        let content_html = markdown
+            .filter(|_| info.secret.is_none())
+            .and_then(parse_markdown)
+            .map(|content| content.html_content);
+        Self {
+            url: config.url(&format!("/blog/{}", info.aid)),
+            title: info.title.clone(),
+            summary: info.summary.clone(),
+            content_html,
+            categories: info.tags.iter().chain(&info.categories).cloned().collect(),
+            published: info.created_at,
+            updated: info.updated_at,
+        }
+    }
+}
+
+/// 订阅源，按发布时间从新到旧排列
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub config: FeedConfig,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    pub fn new(config: FeedConfig, mut entries: Vec<FeedEntry>) -> Self {
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.published));
+        entries.truncate(FEED_LIMIT);
+        Self { config, entries }
+    }
+
+    /// 整个订阅源的更新时间，没有文章时为 Unix 纪元
+    pub fn updated(&self) -> DateTime<Utc> {
+        self.entries.iter().map(|entry| entry.updated).max().unwrap_or_default()
+    }
+
+    /// 三种格式的文件名和内容
+    pub fn render_all(&self) -> [(&'static str, String); 3] {
+        [
+            (RSS_FILE, rss::render(self)),
+            (ATOM_FILE, atom::render(self)),
+            (JSON_FEED_FILE, json::render(self)),
+        ]
+    }
+}
+
+/// 转义 XML 文本和属性值
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 不允许除制表、换行、回车以外的控制字符
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+
+    use quick_xml::{events::Event, Reader};
+
+    use super::*;
+
+    /// 解析出的 XML 元素：从根元素开始的路径（如 `rss/channel/title`）、属性和文本
+    pub struct Element {
+        pub path: String,
+        pub attributes: HashMap<String, String>,
+        pub text: String,
+    }
+
+    /// 按文档顺序列出所有元素，同时检查 XML 格式是否正确
+    pub fn parse_xml(xml: &str) -> Vec<Element> {
+        let mut reader = Reader::from_str(xml);
+        let mut elements: Vec<Element> = Vec::new();
+        // 尚未结束的元素在 `elements` 中的下标
+        let mut open: Vec<usize> = Vec::new();
+        loop {
+            let event = reader.read_event().expect("feed should be well-formed XML");
+            match &event {
+                Event::Start(tag) | Event::Empty(tag) => {
+                    let name = String::from_utf8(tag.name().as_ref().to_vec()).unwrap();
+                    let path = match open.last() {
+                        Some(&parent) => format!("{}/{name}", elements[parent].path),
+                        None => name,
+                    };
+                    let attributes = tag
+                        .attributes()
+                        .map(|attr| {
+                            let attr = attr.unwrap();
+                            let key = String::from_utf8(attr.key.as_ref().to_vec()).unwrap();
+                            (key, attr.unescape_value().unwrap().into_owned())
+                        })
+                        .collect();
+                    elements.push(Element { path, attributes, text: String::new() });
+                    if matches!(event, Event::Start(_)) {
+                        open.push(elements.len() - 1);
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(&idx) = open.last() {
+                        elements[idx].text.push_str(&text.unescape().unwrap());
+                    }
+                }
+                Event::End(_) => {
+                    open.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        elements
+    }
+
+    /// 路径为 `path` 的所有元素的文本
+    pub fn texts<'a>(elements: &'a [Element], path: &str) -> Vec<&'a str> {
+        elements.iter().filter(|element| element.path == path).map(|element| element.text.as_str()).collect()
+    }
+
+    pub fn sample_feed() -> Feed {
+        let mut config = FeedConfig::new("Rusite", "A <Rust> blog", "https://example.com/");
+        config.author = Some("Ryan".to_string());
+        let info = |aid: &str, title: &str, day: i64| {
+            ArticleInfo::sample(aid)
+                .with_title(title)
+                .with_summary(&format!("Summary of {title}"))
+                .with_tags(&["rust"])
+                .with_categories(&["notes"])
+                .created(day * 86_400)
+                .updated(day * 86_400 + 3_600)
+        };
+        let entries = vec![
+            FeedEntry::new(&config, &info("a", "Tom & Jerry", 1), Some("# Hello\n\nsome `code` & <b>tags</b>")),
+            FeedEntry::new(&config, &info("b", "Locked", 2).with_secret("pw"), Some("secret body")),
+        ];
+        Feed::new(config, entries)
+    }
+
+    #[test]
+    fn entries_are_sorted_and_secret_content_is_omitted() {
+        let feed = sample_feed();
+        assert_eq!(feed.entries[0].url, "https://example.com/blog/b");
+        assert_eq!(feed.entries[0].content_html, None);
+        assert!(feed.entries[1].content_html.as_ref().unwrap().contains("<h1"));
+        assert_eq!(feed.entries[1].categories, ["rust", "notes"]);
+        assert_eq!(feed.updated(), feed.entries[0].updated);
+    }
+
+    #[test]
+    fn escapes_xml_special_characters() {
+        assert_eq!(escape_xml("a < b & \"c\"\u{1}"), "a &lt; b &amp; &quot;c&quot;");
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Write;
+
+use crate::feeds::{escape_xml, Feed, RSS_FILE};
+
+/// 生成 RSS 2.0，正文放在 `content:encoded` 中。
+/// RSS 的 `<author>` 要求是邮箱地址，作者名使用 `dc:creator`
+pub fn render(feed: &Feed) -> String {
+    let config = &feed.config;
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/">"#);
+    xml.push_str("<channel>");
+    let _ = write!(
+        xml,
+        "<title>{}</title><link>{}</link><description>{}</description><language>{}</language><lastBuildDate>{}</lastBuildDate>",
+        escape_xml(&config.title),
+        escape_xml(&config.url("/")),
+        escape_xml(&config.description),
+        escape_xml(&config.language),
+        feed.updated().to_rfc2822(),
+    );
+    let _ = write!(
+        xml,
+        r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+        escape_xml(&config.url(&format!("/{RSS_FILE}"))),
+    );
+    for entry in &feed.entries {
+        xml.push_str("<item>");
+        let _ = write!(
+            xml,
+            r#"<title>{}</title><link>{url}</link><guid isPermaLink="true">{url}</guid><description>{}</description><pubDate>{}</pubDate>"#,
+            escape_xml(&entry.title),
+            escape_xml(&entry.summary),
+            entry.published.to_rfc2822(),
+            url = escape_xml(&entry.url),
+        );
+        if let Some(author) = &config.author {
+            let _ = write!(xml, "<dc:creator>{}</dc:creator>", escape_xml(author));
+        }
+        for category in &entry.categories {
+            let _ = write!(xml, "<category>{}</category>", escape_xml(category));
+        }
+        if let Some(html) = &entry.content_html {
+            let _ = write!(xml, "<content:encoded>{}</content:encoded>", escape_xml(html));
+        }
+        xml.push_str("</item>");
+    }
+    xml.push_str("</channel></rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::tests::{parse_xml, sample_feed, texts};
+
+    #[test]
+    fn renders_required_rss_fields() {
+        let elements = parse_xml(&render(&sample_feed()));
+        assert_eq!(elements[0].path, "rss");
+        assert_eq!(elements[0].attributes["version"], "2.0");
+        assert_eq!(elements[0].attributes["xmlns:dc"], "http://purl.org/dc/elements/1.1/");
+
+        assert_eq!(texts(&elements, "rss/channel/title"), ["Rusite"]);
+        assert_eq!(texts(&elements, "rss/channel/link"), ["https://example.com/"]);
+        assert_eq!(texts(&elements, "rss/channel/description"), ["A <Rust> blog"]);
+        assert_eq!(texts(&elements, "rss/channel/lastBuildDate"), ["Sat, 3 Jan 1970 01:00:00 +0000"]);
+        let self_link = elements.iter().find(|element| element.path == "rss/channel/atom:link").unwrap();
+        assert_eq!(self_link.attributes["href"], "https://example.com/rss.xml");
+        assert_eq!(self_link.attributes["rel"], "self");
+
+        assert_eq!(texts(&elements, "rss/channel/item/title"), ["Locked", "Tom & Jerry"]);
+        assert_eq!(texts(&elements, "rss/channel/item/link"), ["https://example.com/blog/b", "https://example.com/blog/a"]);
+        assert_eq!(texts(&elements, "rss/channel/item/guid"), texts(&elements, "rss/channel/item/link"));
+        assert_eq!(texts(&elements, "rss/channel/item/description"), ["Summary of Locked", "Summary of Tom & Jerry"]);
+        assert_eq!(texts(&elements, "rss/channel/item/pubDate"), ["Sat, 3 Jan 1970 00:00:00 +0000", "Fri, 2 Jan 1970 00:00:00 +0000"]);
+        assert_eq!(texts(&elements, "rss/channel/item/dc:creator"), ["Ryan", "Ryan"]);
+        assert!(texts(&elements, "rss/channel/item/author").is_empty());
+        assert_eq!(texts(&elements, "rss/channel/item/category"), ["rust", "notes", "rust", "notes"]);
+        let content = texts(&elements, "rss/channel/item/content:encoded");
+        assert_eq!(content.len(), 1);
+        assert!(content[0].contains("<h1"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+
+use once_cell::sync::Lazy;
+use syntect::{
+    highlighting::ThemeSet,
+    html::{css_for_theme_with_class_style, line_tokens_to_classed_spans, ClassStyle},
+    parsing::{ParseState, Scope, ScopeStack, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+/// 高亮输出的类名前缀，避免与 Tailwind 的类名冲突
+const CLASS_PREFIX: &str = "hl-";
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: CLASS_PREFIX };
+const LIGHT_THEME: &str = "InspiredGitHub";
+const DARK_THEME: &str = "base16-ocean.dark";
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_CSS: Lazy<String> = Lazy::new(build_theme_css);
+
+/// 代码块 info string 解析结果，例如 ```` ```rust {3,5-7} ````
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeFence {
+    pub lang: Option<String>,
+    /// 需要高亮标注的行号（从 1 开始）
+    pub marked_lines: BTreeSet<usize>,
+}
+
+impl CodeFence {
+    pub fn parse(info: &str) -> Self {
+        let (lang_part, marks) = match info.split_once('{') {
+            Some((lang, rest)) => (lang, rest.split('}').next().unwrap_or_default()),
+            None => (info, ""),
+        };
+        // `rust,ignore` 之类的写法只取语言部分
+        let lang = lang_part
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .find(|token| !token.is_empty())
+            .map(str::to_string);
+        Self {
+            lang,
+            marked_lines: parse_line_ranges(marks),
+        }
+    }
+}
+
+// 解析 `3,5-7` 形式的行号列表，无法解析的部分直接忽略
+fn parse_line_ranges(spec: &str) -> BTreeSet<usize> {
+    let mut lines = BTreeSet::new();
+    for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                    lines.extend(start.max(1)..=end);
+                }
+            }
+            None => {
+                if let Ok(line) = part.parse::<usize>() {
+                    lines.insert(line);
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// 将代码渲染为带类名的 HTML，每行包在 `span.code-line` 中，
+/// 行号通过 `data-line` 由 CSS 显示。未知语言按纯文本输出。
+pub fn highlight_code(code: &str, fence: &CodeFence) -> String {
+    let syntax = fence
+        .lang
+        .as_deref()
+        .and_then(|lang| SYNTAX_SET.find_syntax_by_token(lang))
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+    let mut parse_state = ParseState::new(syntax);
+    let mut scope_stack = ScopeStack::new();
+    let mut body = String::with_capacity(code.len() * 2);
+
+    for (index, line) in LinesWithEndings::from(code).enumerate() {
+        let number = index + 1;
+        let tokens = parse_state
+            .parse_line(line, &SYNTAX_SET)
+            .ok()
+            .and_then(|ops| {
+                // 上一行未闭合的作用域需要在本行重新打开，保证每行的 span 自成一体
+                let mut html: String = scope_stack.as_slice().iter().map(|scope| open_span(*scope)).collect();
+                let (spans, _) = line_tokens_to_classed_spans(line, &ops, CLASS_STYLE, &mut scope_stack).ok()?;
+                html.push_str(&spans);
+                html.push_str(&"</span>".repeat(scope_stack.len()));
+                Some(html)
+            })
+            .unwrap_or_else(|| escape_html(line));
+        let class = if fence.marked_lines.contains(&number) { "code-line marked" } else { "code-line" };
+        body.push_str(&format!(
+            r#"<span class="{class}" data-line="{number}">{}</span>"#,
+            tokens.replace(['\r', '\n'], "")
+        ));
+    }
+
+    let lang = fence.lang.as_deref().map(escape_html).unwrap_or_else(|| "text".to_string());
+    format!(r#"<pre class="code-block {CLASS_PREFIX}code"><code class="language-{lang}">{body}</code></pre>"#)
+}
+
+fn open_span(scope: Scope) -> String {
+    let classes = scope
+        .build_string()
+        .split('.')
+        .map(|atom| format!("{CLASS_PREFIX}{atom}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(r#"<span class="{classes}">"#)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// 代码高亮主题：亮色主题直接生效，暗色主题放在 `prefers-color-scheme: dark` 媒体查询中，跟随站点的暗色模式
+pub fn theme_css() -> &'static str {
+    &THEME_CSS
+}
+
+fn build_theme_css() -> String {
+    let themes = ThemeSet::load_defaults();
+    let light = themes
+        .themes
+        .get(LIGHT_THEME)
+        .and_then(|theme| css_for_theme_with_class_style(theme, CLASS_STYLE).ok())
+        .unwrap_or_default();
+    let dark = themes
+        .themes
+        .get(DARK_THEME)
+        .and_then(|theme| css_for_theme_with_class_style(theme, CLASS_STYLE).ok())
+        .map(|css| dark_css(&css))
+        .unwrap_or_default();
+    format!("{light}\n{dark}\n{LINE_CSS}")
+}
+
+const LINE_CSS: &str = r#"
+.code-block { padding: 1em 0; overflow-x: auto; border-radius: 0.375rem; }
+.code-block .code-line { display: block; min-height: 1.5em; padding-right: 1em; }
+.code-block .code-line::before {
+    content: attr(data-line);
+    display: inline-block;
+    width: 2.5em;
+    margin-right: 1em;
+    text-align: right;
+    opacity: 0.5;
+}
+.code-block .code-line.marked { background-color: rgba(250, 204, 21, 0.2); }
+"#;
+
+// 暗色主题只在系统处于暗色模式时生效，与 Tailwind `dark:` 的默认策略一致
+fn dark_css(css: &str) -> String {
+    format!("@media (prefers-color-scheme: dark) {{\n{}\n}}", css.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_language_and_marked_lines() {
+        let fence = CodeFence::parse("rust {3,5-7}");
+        assert_eq!(fence.lang.as_deref(), Some("rust"));
+        assert_eq!(fence.marked_lines, BTreeSet::from([3, 5, 6, 7]));
+
+        let fence = CodeFence::parse("rust,ignore");
+        assert_eq!(fence.lang.as_deref(), Some("rust"));
+        assert!(fence.marked_lines.is_empty());
+    }
+
+    #[test]
+    fn highlights_each_line_with_balanced_spans() {
+        let code = "/* multi\nline */\nfn main() {}\n";
+        let html = highlight_code(code, &CodeFence::parse("rust {2}"));
+        assert_eq!(html.matches("<span").count(), html.matches("</span>").count());
+        assert_eq!(html.matches("class=\"code-line").count(), 3);
+        assert!(html.contains(r#"<span class="code-line marked" data-line="2">"#));
+        assert!(html.contains("hl-comment"));
+    }
+
+    #[test]
+    fn unknown_language_is_escaped_plain_text() {
+        let html = highlight_code("<b>&</b>", &CodeFence::parse("nope"));
+        assert!(html.contains("&lt;b&gt;&amp;&lt;/b&gt;"));
+    }
+
+    #[test]
+    fn dark_theme_follows_color_scheme() {
+        let css = dark_css("/* x */\n.hl-code, .hl-a { color: red; }\n");
+        assert_eq!(css, "@media (prefers-color-scheme: dark) {\n/* x */\n.hl-code, .hl-a { color: red; }\n}");
+        let css = theme_css();
+        let (light, dark) = css.split_once("@media (prefers-color-scheme: dark) {").unwrap();
+        assert!(light.contains(".hl-code"));
+        assert!(dark.contains(".hl-code"));
+    }
+}
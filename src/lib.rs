@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 pub mod app;
 pub mod api;
 pub mod components;
@@ -5,4 +7,14 @@ pub mod pages;
 pub mod models;
 pub mod state;
 pub mod utils;
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod highlight;
+pub mod math;
+pub mod search;
+pub mod feeds;
+pub mod sitemap;
+pub mod seo;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod export;
+#[cfg(feature = "ssr")]
+pub mod ssr;
\ No newline at end of file
@@ -1,8 +1,13 @@
+#![recursion_limit = "256"]
+
 use rusite_front_ending::app::App;
 
 
 pub fn main() {
     dotenv::dotenv().ok();
     console_error_panic_hook::set_once();
+    #[cfg(feature = "hydrate")]
+    leptos::mount::hydrate_body(App);
+    #[cfg(not(feature = "hydrate"))]
     leptos::mount::mount_to_body(App);
 }
\ No newline at end of file
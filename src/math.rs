@@ -0,0 +1,562 @@
+use std::{error::Error, fmt};
+
+/// TeX 转 MathML 时的解析错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathError {
+    UnexpectedEnd,
+    Unexpected(String),
+    UnknownCommand(String),
+    UnknownEnvironment(String),
+    MismatchedEnvironment { expected: String, found: String },
+    MissingBase(char),
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::UnexpectedEnd => write!(f, "unexpected end of formula"),
+            MathError::Unexpected(token) => write!(f, "unexpected `{token}`"),
+            MathError::UnknownCommand(name) => write!(f, "unknown command `\\{name}`"),
+            MathError::UnknownEnvironment(name) => write!(f, "unknown environment `{name}`"),
+            MathError::MismatchedEnvironment { expected, found } => {
+                write!(f, "`\\begin{{{expected}}}` closed by `\\end{{{found}}}`")
+            }
+            MathError::MissingBase(c) => write!(f, "`{c}` without a base"),
+        }
+    }
+}
+
+impl Error for MathError {}
+
+/// 渲染公式，解析失败时退回到带错误提示的原始 TeX
+pub fn render_math(tex: &str, display: bool) -> String {
+    match tex_to_mathml(tex, display) {
+        Ok(mathml) => mathml,
+        Err(err) => {
+            let class = if display { "math math-display math-error" } else { "math-error" };
+            format!(r#"<code class="{class}" title="{}">{}</code>"#, escape(&err.to_string()), escape(tex))
+        }
+    }
+}
+
+/// 将 TeX 公式转换为 MathML，支持常用的命令子集（分式、根式、上下标、
+/// 希腊字母、常见运算符、`\left`/`\right` 以及矩阵类环境）
+pub fn tex_to_mathml(tex: &str, display: bool) -> Result<String, MathError> {
+    let mut parser = TexParser::new(tex, display);
+    let row = parser.parse_row()?;
+    if let Some(token) = parser.peek_token() {
+        return Err(MathError::Unexpected(token));
+    }
+    let (display_attr, class) = if display { ("block", "math math-display") } else { ("inline", "math math-inline") };
+    Ok(format!(
+        r#"<math display="{display_attr}" class="{class}"><semantics>{}<annotation encoding="application/x-tex">{}</annotation></semantics></math>"#,
+        mrow(row),
+        escape(tex.trim())
+    ))
+}
+
+const GREEK: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"), ("epsilon", "ϵ"),
+    ("varepsilon", "ε"), ("zeta", "ζ"), ("eta", "η"), ("theta", "θ"), ("vartheta", "ϑ"),
+    ("iota", "ι"), ("kappa", "κ"), ("lambda", "λ"), ("mu", "μ"), ("nu", "ν"), ("xi", "ξ"),
+    ("pi", "π"), ("varpi", "ϖ"), ("rho", "ρ"), ("varrho", "ϱ"), ("sigma", "σ"),
+    ("varsigma", "ς"), ("tau", "τ"), ("upsilon", "υ"), ("phi", "ϕ"), ("varphi", "φ"),
+    ("chi", "χ"), ("psi", "ψ"), ("omega", "ω"), ("ell", "ℓ"), ("hbar", "ℏ"), ("partial", "∂"),
+    ("nabla", "∇"), ("infty", "∞"), ("emptyset", "∅"), ("aleph", "ℵ"),
+];
+
+const UPPER_GREEK: &[(&str, &str)] = &[
+    ("Gamma", "Γ"), ("Delta", "Δ"), ("Theta", "Θ"), ("Lambda", "Λ"), ("Xi", "Ξ"), ("Pi", "Π"),
+    ("Sigma", "Σ"), ("Upsilon", "Υ"), ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"),
+];
+
+const OPERATORS: &[(&str, &str)] = &[
+    ("times", "×"), ("cdot", "⋅"), ("div", "÷"), ("pm", "±"), ("mp", "∓"), ("ast", "∗"),
+    ("circ", "∘"), ("bullet", "∙"), ("oplus", "⊕"), ("otimes", "⊗"), ("leq", "≤"), ("le", "≤"),
+    ("geq", "≥"), ("ge", "≥"), ("neq", "≠"), ("ne", "≠"), ("approx", "≈"), ("equiv", "≡"),
+    ("sim", "∼"), ("simeq", "≃"), ("cong", "≅"), ("propto", "∝"), ("ll", "≪"), ("gg", "≫"),
+    ("in", "∈"), ("notin", "∉"), ("ni", "∋"), ("subset", "⊂"), ("subseteq", "⊆"),
+    ("supset", "⊃"), ("supseteq", "⊇"), ("cup", "∪"), ("cap", "∩"), ("setminus", "∖"),
+    ("land", "∧"), ("wedge", "∧"), ("lor", "∨"), ("vee", "∨"), ("neg", "¬"), ("lnot", "¬"),
+    ("forall", "∀"), ("exists", "∃"), ("to", "→"), ("rightarrow", "→"), ("leftarrow", "←"),
+    ("gets", "←"), ("leftrightarrow", "↔"), ("Rightarrow", "⇒"), ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"), ("implies", "⟹"), ("iff", "⟺"), ("mapsto", "↦"), ("uparrow", "↑"),
+    ("downarrow", "↓"), ("mid", "∣"), ("parallel", "∥"), ("perp", "⊥"), ("angle", "∠"),
+    ("ldots", "…"), ("dots", "…"), ("cdots", "⋯"), ("vdots", "⋮"), ("ddots", "⋱"),
+    ("langle", "⟨"), ("rangle", "⟩"), ("lfloor", "⌊"), ("rfloor", "⌋"), ("lceil", "⌈"),
+    ("rceil", "⌉"), ("lvert", "|"), ("rvert", "|"), ("vert", "|"), ("Vert", "‖"),
+    ("lVert", "‖"), ("rVert", "‖"), ("prime", "′"), ("triangle", "△"), ("star", "⋆"),
+];
+
+/// 大型运算符，展示模式下上下标放在正上方和正下方
+const BIG_OPERATORS: &[(&str, &str)] = &[
+    ("sum", "∑"), ("prod", "∏"), ("coprod", "∐"), ("bigcup", "⋃"), ("bigcap", "⋂"),
+    ("bigoplus", "⨁"), ("bigotimes", "⨂"),
+];
+
+const INTEGRALS: &[(&str, &str)] = &[("int", "∫"), ("iint", "∬"), ("iiint", "∭"), ("oint", "∮")];
+
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh",
+    "tanh", "log", "ln", "lg", "exp", "det", "dim", "deg", "arg", "ker", "gcd", "hom", "Pr",
+];
+
+/// 带 limits 的函数名，例如 `\lim_{x \to 0}`
+const LIMIT_FUNCTIONS: &[&str] = &["lim", "max", "min", "sup", "inf", "liminf", "limsup", "argmax", "argmin"];
+
+const ACCENTS: &[(&str, &str)] = &[
+    ("hat", "^"), ("widehat", "^"), ("bar", "¯"), ("overline", "¯"), ("vec", "→"),
+    ("overrightarrow", "→"), ("dot", "˙"), ("ddot", "¨"), ("tilde", "~"), ("widetilde", "~"),
+];
+
+const FONTS: &[(&str, &str)] = &[
+    ("mathbf", "bold"), ("mathit", "italic"), ("mathbb", "double-struck"), ("mathcal", "script"),
+    ("mathscr", "script"), ("mathfrak", "fraktur"), ("mathsf", "sans-serif"),
+    ("mathtt", "monospace"), ("boldsymbol", "bold-italic"), ("mathrm", "normal"),
+];
+
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.167em"), (":", "0.222em"), (">", "0.222em"), (";", "0.278em"), (" ", "0.278em"),
+    ("!", "-0.167em"), ("quad", "1em"), ("qquad", "2em"),
+];
+
+fn lookup(table: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+    table.iter().find(|(key, _)| *key == name).map(|(_, value)| *value)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn mrow(items: Vec<String>) -> String {
+    if items.len() == 1 {
+        items.into_iter().next().unwrap_or_default()
+    } else {
+        format!("<mrow>{}</mrow>", items.concat())
+    }
+}
+
+fn mo(symbol: &str) -> String {
+    format!("<mo>{}</mo>", escape(symbol))
+}
+
+fn fence(symbol: &str) -> String {
+    if symbol.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<mo fence="true" stretchy="true">{}</mo>"#, escape(symbol))
+    }
+}
+
+/// 解析出的一个元素，`limits` 表示上下标应当放在正上/下方
+struct Atom {
+    html: String,
+    limits: bool,
+}
+
+impl Atom {
+    fn new(html: String) -> Self {
+        Self { html, limits: false }
+    }
+}
+
+struct TexParser {
+    chars: Vec<char>,
+    pos: usize,
+    display: bool,
+}
+
+impl TexParser {
+    fn new(tex: &str, display: bool) -> Self {
+        Self { chars: tex.chars().collect(), pos: 0, display }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), MathError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(MathError::Unexpected(c.to_string())),
+            None => Err(MathError::UnexpectedEnd),
+        }
+    }
+
+    /// 读取 `\` 之后的命令名：字母序列或单个符号
+    fn read_command_name(&mut self) -> Result<String, MathError> {
+        let first = self.next().ok_or(MathError::UnexpectedEnd)?;
+        let mut name = first.to_string();
+        if first.is_ascii_alphabetic() {
+            while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+                name.push(c);
+                self.pos += 1;
+            }
+        }
+        Ok(name)
+    }
+
+    /// 不消费地查看下一个记号，用于判断行/分组的结束
+    fn peek_token(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let token = match self.next()? {
+            '\\' => format!("\\{}", self.read_command_name().unwrap_or_default()),
+            c => c.to_string(),
+        };
+        self.pos = start;
+        Some(token)
+    }
+
+    /// 读取一行，遇到 `}`、`&`、`\\`、`\right`、`\end` 或结尾时停止（不消费）
+    fn parse_row(&mut self) -> Result<Vec<String>, MathError> {
+        let mut items = Vec::new();
+        while let Some(token) = self.peek_token() {
+            if matches!(token.as_str(), "}" | "&" | "\\\\" | "\\right" | "\\end") {
+                break;
+            }
+            items.push(self.parse_scripted()?);
+        }
+        Ok(items)
+    }
+
+    fn parse_group(&mut self) -> Result<String, MathError> {
+        self.expect('{')?;
+        let row = self.parse_row()?;
+        self.expect('}')?;
+        Ok(mrow(row))
+    }
+
+    /// 读取 `{...}` 中的原始文本，用于 `\text` 和环境名
+    fn read_raw_group(&mut self) -> Result<String, MathError> {
+        self.expect('{')?;
+        let mut depth = 0;
+        let mut raw = String::new();
+        loop {
+            match self.next().ok_or(MathError::UnexpectedEnd)? {
+                '{' => depth += 1,
+                '}' if depth == 0 => return Ok(raw),
+                '}' => depth -= 1,
+                _ => {}
+            }
+            raw.push(self.chars[self.pos - 1]);
+        }
+    }
+
+    /// 命令参数：`{...}` 分组或单个记号
+    fn parse_argument(&mut self) -> Result<String, MathError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_group(),
+            Some(_) => Ok(self.parse_atom(true)?.html),
+            None => Err(MathError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_scripted(&mut self) -> Result<String, MathError> {
+        let base = self.parse_atom(false)?;
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('_') if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(self.parse_argument()?);
+                }
+                Some('^') if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(self.parse_argument()?);
+                }
+                Some('\'') => {
+                    self.pos += 1;
+                    let prime = mo("′");
+                    sup = Some(match sup {
+                        Some(existing) => format!("<mrow>{existing}{prime}</mrow>"),
+                        None => prime,
+                    });
+                }
+                _ => break,
+            }
+        }
+        let limits = base.limits && self.display;
+        let (under, over, both) = if limits { ("munder", "mover", "munderover") } else { ("msub", "msup", "msubsup") };
+        let base = base.html;
+        Ok(match (sub, sup) {
+            (None, None) => base,
+            (Some(sub), None) => format!("<{under}>{base}{sub}</{under}>"),
+            (None, Some(sup)) => format!("<{over}>{base}{sup}</{over}>"),
+            (Some(sub), Some(sup)) => format!("<{both}>{base}{sub}{sup}</{both}>"),
+        })
+    }
+
+    /// 解析一个元素；`single` 为真时数字只取一位（`x^12` 的上标是 `1`）
+    fn parse_atom(&mut self, single: bool) -> Result<Atom, MathError> {
+        self.skip_whitespace();
+        let c = self.next().ok_or(MathError::UnexpectedEnd)?;
+        let html = match c {
+            '{' => {
+                self.pos -= 1;
+                self.parse_group()?
+            }
+            '\\' => return self.parse_command(),
+            '^' | '_' => return Err(MathError::MissingBase(c)),
+            '}' | '&' => return Err(MathError::Unexpected(c.to_string())),
+            '~' => r#"<mspace width="0.278em"/>"#.to_string(),
+            '-' => mo("−"),
+            c if c.is_ascii_digit() || (c == '.' && self.peek().is_some_and(|n| n.is_ascii_digit())) => {
+                let mut number = c.to_string();
+                while !single
+                    && let Some(n) = self.peek().filter(|n| n.is_ascii_digit() || *n == '.')
+                {
+                    number.push(n);
+                    self.pos += 1;
+                }
+                format!("<mn>{number}</mn>")
+            }
+            c if c.is_alphabetic() => format!("<mi>{c}</mi>"),
+            c => mo(&c.to_string()),
+        };
+        Ok(Atom::new(html))
+    }
+
+    fn parse_command(&mut self) -> Result<Atom, MathError> {
+        let name = self.read_command_name()?;
+        let name = name.as_str();
+        if let Some(width) = lookup(SPACES, name) {
+            return Ok(Atom::new(format!(r#"<mspace width="{width}"/>"#)));
+        }
+        if let Some(symbol) = lookup(GREEK, name) {
+            return Ok(Atom::new(format!("<mi>{symbol}</mi>")));
+        }
+        if let Some(symbol) = lookup(UPPER_GREEK, name) {
+            return Ok(Atom::new(format!(r#"<mi mathvariant="normal">{symbol}</mi>"#)));
+        }
+        if let Some(symbol) = lookup(OPERATORS, name) {
+            return Ok(Atom::new(mo(symbol)));
+        }
+        if let Some(symbol) = lookup(BIG_OPERATORS, name) {
+            return Ok(Atom { html: format!(r#"<mo largeop="true">{symbol}</mo>"#), limits: true });
+        }
+        if let Some(symbol) = lookup(INTEGRALS, name) {
+            return Ok(Atom::new(format!(r#"<mo largeop="true">{symbol}</mo>"#)));
+        }
+        if FUNCTIONS.contains(&name) {
+            return Ok(Atom::new(format!("<mi>{name}</mi>")));
+        }
+        if LIMIT_FUNCTIONS.contains(&name) {
+            return Ok(Atom { html: format!("<mi>{name}</mi>"), limits: true });
+        }
+        if let Some(accent) = lookup(ACCENTS, name) {
+            let base = self.parse_argument()?;
+            return Ok(Atom::new(format!(r#"<mover accent="true">{base}{}</mover>"#, mo(accent))));
+        }
+        if let Some(variant) = lookup(FONTS, name) {
+            return self.parse_font(variant).map(Atom::new);
+        }
+        let html = match name {
+            "{" | "}" | "|" | "%" | "$" | "#" | "&" | "_" => mo(if name == "|" { "‖" } else { name }),
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                format!("<mfrac>{numerator}{denominator}</mfrac>")
+            }
+            "binom" => {
+                let n = self.parse_argument()?;
+                let k = self.parse_argument()?;
+                format!(r#"<mrow>{}<mfrac linethickness="0">{n}{k}</mfrac>{}</mrow>"#, fence("("), fence(")"))
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                if self.peek() == Some('[') {
+                    self.pos += 1;
+                    let mut index = Vec::new();
+                    while self.peek_token().is_some_and(|token| token != "]") {
+                        index.push(self.parse_scripted()?);
+                    }
+                    self.expect(']')?;
+                    let radicand = self.parse_argument()?;
+                    format!("<mroot>{radicand}{}</mroot>", mrow(index))
+                } else {
+                    format!("<msqrt>{}</msqrt>", self.parse_argument()?)
+                }
+            }
+            "text" | "textrm" | "mbox" => format!("<mtext>{}</mtext>", escape(&self.read_raw_group()?)),
+            "operatorname" => format!("<mi>{}</mi>", escape(self.read_raw_group()?.trim())),
+            "underline" => format!(r#"<munder accentunder="true">{}{}</munder>"#, self.parse_argument()?, mo("_")),
+            "left" => self.parse_left()?,
+            "begin" => self.parse_environment()?,
+            _ => return Err(MathError::UnknownCommand(name.to_string())),
+        };
+        Ok(Atom::new(html))
+    }
+
+    /// 字体命令：参数只含字母数字时逐个加上 `mathvariant`，否则按普通公式处理
+    fn parse_font(&mut self, variant: &str) -> Result<String, MathError> {
+        self.skip_whitespace();
+        if self.peek() != Some('{') {
+            return self.parse_argument();
+        }
+        let start = self.pos;
+        let raw = self.read_raw_group()?;
+        if !raw.chars().all(|c| c.is_ascii_alphanumeric() || c.is_whitespace()) {
+            self.pos = start;
+            return self.parse_group();
+        }
+        let items = raw
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                let tag = if c.is_ascii_digit() { "mn" } else { "mi" };
+                format!(r#"<{tag} mathvariant="{variant}">{c}</{tag}>"#)
+            })
+            .collect();
+        Ok(mrow(items))
+    }
+
+    fn parse_delimiter(&mut self) -> Result<String, MathError> {
+        self.skip_whitespace();
+        match self.next().ok_or(MathError::UnexpectedEnd)? {
+            '.' => Ok(String::new()),
+            '\\' => {
+                let name = self.read_command_name()?;
+                match name.as_str() {
+                    "{" | "}" => Ok(name),
+                    "|" => Ok("‖".to_string()),
+                    _ => lookup(OPERATORS, &name)
+                        .map(str::to_string)
+                        .ok_or(MathError::UnknownCommand(name)),
+                }
+            }
+            c => Ok(c.to_string()),
+        }
+    }
+
+    fn parse_left(&mut self) -> Result<String, MathError> {
+        let open = self.parse_delimiter()?;
+        let row = self.parse_row()?;
+        match self.peek_token().as_deref() {
+            Some("\\right") => self.pos += "\\right".len(),
+            Some(token) => return Err(MathError::Unexpected(token.to_string())),
+            None => return Err(MathError::UnexpectedEnd),
+        }
+        let close = self.parse_delimiter()?;
+        Ok(format!("<mrow>{}{}{}</mrow>", fence(&open), row.concat(), fence(&close)))
+    }
+
+    fn parse_environment(&mut self) -> Result<String, MathError> {
+        let name = self.read_raw_group()?;
+        let (open, close, align) = match name.as_str() {
+            "matrix" | "smallmatrix" => ("", "", "center"),
+            "pmatrix" => ("(", ")", "center"),
+            "bmatrix" => ("[", "]", "center"),
+            "Bmatrix" => ("{", "}", "center"),
+            "vmatrix" => ("|", "|", "center"),
+            "Vmatrix" => ("‖", "‖", "center"),
+            "cases" => ("{", "", "left left"),
+            "aligned" | "align" | "align*" | "split" => ("", "", "right left"),
+            "gathered" | "gather" | "gather*" => ("", "", "center"),
+            _ => return Err(MathError::UnknownEnvironment(name)),
+        };
+
+        let mut rows = Vec::new();
+        let mut cells = Vec::new();
+        loop {
+            cells.push(format!("<mtd>{}</mtd>", mrow(self.parse_row()?)));
+            let token = self.peek_token().ok_or(MathError::UnexpectedEnd)?;
+            self.pos += token.chars().count();
+            match token.as_str() {
+                "&" => {}
+                "\\\\" => rows.push(format!("<mtr>{}</mtr>", std::mem::take(&mut cells).concat())),
+                "\\end" => {
+                    let found = self.read_raw_group()?;
+                    if found != name {
+                        return Err(MathError::MismatchedEnvironment { expected: name, found });
+                    }
+                    break;
+                }
+                _ => return Err(MathError::Unexpected(token)),
+            }
+        }
+        // 末尾的 `\\` 不产生空行
+        if !(cells.len() == 1 && cells[0] == "<mtd><mrow></mrow></mtd>") {
+            rows.push(format!("<mtr>{}</mtr>", cells.concat()));
+        }
+        Ok(format!(
+            r#"<mrow>{}<mtable columnalign="{align}">{}</mtable>{}</mrow>"#,
+            fence(open),
+            rows.concat(),
+            fence(close)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(tex: &str) -> String {
+        let html = tex_to_mathml(tex, false).unwrap();
+        let start = html.find("<semantics>").unwrap() + "<semantics>".len();
+        let end = html.find("<annotation").unwrap();
+        html[start..end].to_string()
+    }
+
+    #[test]
+    fn renders_scripts_and_fractions() {
+        assert_eq!(body("x^2"), "<msup><mi>x</mi><mn>2</mn></msup>");
+        assert_eq!(body("a_{i}^{n}"), "<msubsup><mi>a</mi><mi>i</mi><mi>n</mi></msubsup>");
+        assert_eq!(body(r"\frac{1}{2}"), "<mfrac><mn>1</mn><mn>2</mn></mfrac>");
+        assert_eq!(body("x^12"), "<mrow><msup><mi>x</mi><mn>1</mn></msup><mn>2</mn></mrow>");
+    }
+
+    #[test]
+    fn big_operators_use_limits_in_display_mode() {
+        let inline = tex_to_mathml(r"\sum_{i=1}^n i", false).unwrap();
+        assert!(inline.contains("<msubsup>"));
+        let display = tex_to_mathml(r"\sum_{i=1}^n i", true).unwrap();
+        assert!(display.contains("<munderover>"));
+        assert!(display.starts_with(r#"<math display="block""#));
+    }
+
+    #[test]
+    fn renders_environments_and_fences() {
+        let html = body(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}");
+        assert_eq!(html.matches("<mtr>").count(), 2);
+        assert_eq!(html.matches("<mtd>").count(), 4);
+        let html = body(r"\left( \frac{a}{b} \right]");
+        assert!(html.contains(r#"<mo fence="true" stretchy="true">(</mo>"#));
+        assert!(html.contains(r#"<mo fence="true" stretchy="true">]</mo>"#));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(tex_to_mathml(r"\frac{1}", false), Err(MathError::UnexpectedEnd));
+        assert_eq!(tex_to_mathml(r"\foo", false), Err(MathError::UnknownCommand("foo".to_string())));
+        assert_eq!(tex_to_mathml("a}", false), Err(MathError::Unexpected("}".to_string())));
+        assert!(matches!(
+            tex_to_mathml(r"\begin{matrix} a \end{pmatrix}", false),
+            Err(MathError::MismatchedEnvironment { .. })
+        ));
+    }
+
+    #[test]
+    fn falls_back_to_escaped_source() {
+        let html = render_math(r"\foo < 1", false);
+        assert!(html.starts_with(r#"<code class="math-error""#));
+        assert!(html.contains(r"\foo &lt; 1"));
+    }
+}
@@ -0,0 +1,117 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Utc};
+use chrono_tz::Tz;
+
+use crate::models::blog::ArticleInfo;
+
+/// 年月，链接参数格式为 `2025-03`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearMonth {
+    pub year: i32,
+    pub month: u32,
+}
+
+impl YearMonth {
+    /// 时间在指定时区下所属的年月
+    pub fn of(date: DateTime<Utc>, timezone: Tz) -> Self {
+        let local = date.with_timezone(&timezone);
+        Self { year: local.year(), month: local.month() }
+    }
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+impl FromStr for YearMonth {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (year, month) = value.split_once('-').ok_or(())?;
+        let year = year.parse().map_err(|_| ())?;
+        let month = month.parse().map_err(|_| ())?;
+        if (1..=12).contains(&month) {
+            Ok(Self { year, month })
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveMonth {
+    pub month: YearMonth,
+    pub articles: Vec<ArticleInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveYear {
+    pub year: i32,
+    pub count: usize,
+    pub months: Vec<ArchiveMonth>,
+}
+
+/// 按发布时间所在的年、月分组，年月和组内文章都是新的在前
+pub fn group_by_month<'a>(articles: impl IntoIterator<Item = &'a ArticleInfo>, timezone: Tz) -> Vec<ArchiveYear> {
+    let mut articles = articles.into_iter().collect::<Vec<_>>();
+    articles.sort_by_key(|info| std::cmp::Reverse(info.created_at));
+
+    let mut years: Vec<ArchiveYear> = Vec::new();
+    for info in articles {
+        let month = YearMonth::of(info.created_at, timezone);
+        if years.last().is_none_or(|year| year.year != month.year) {
+            years.push(ArchiveYear { year: month.year, count: 0, months: Vec::new() });
+        }
+        let Some(year) = years.last_mut() else {
+            continue;
+        };
+        year.count += 1;
+        match year.months.last_mut() {
+            Some(last) if last.month == month => last.articles.push(info.clone()),
+            _ => year.months.push(ArchiveMonth { month, articles: vec![info.clone()] }),
+        }
+    }
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::{Asia::Shanghai, UTC};
+
+    use super::*;
+
+    fn info(aid: &str, created_at: &str) -> ArticleInfo {
+        ArticleInfo::sample(aid).created(created_at.parse::<DateTime<Utc>>().unwrap().timestamp())
+    }
+
+    #[test]
+    fn parses_and_formats_year_month() {
+        assert_eq!("2025-03".parse(), Ok(YearMonth { year: 2025, month: 3 }));
+        assert_eq!(YearMonth { year: 2025, month: 3 }.to_string(), "2025-03");
+        assert!("2025-13".parse::<YearMonth>().is_err());
+        assert!("2025".parse::<YearMonth>().is_err());
+    }
+
+    #[test]
+    fn groups_newest_first_in_timezone() {
+        let articles = [
+            info("a", "2024-12-31T17:00:00Z"),
+            info("b", "2025-01-15T00:00:00Z"),
+            info("c", "2024-11-02T00:00:00Z"),
+        ];
+        let months = |years: &[ArchiveYear]| {
+            years.iter().flat_map(|year| year.months.iter().map(|month| month.month.to_string())).collect::<Vec<_>>()
+        };
+
+        // 上海时间 2025-01-01 01:00
+        let shanghai = group_by_month(&articles, Shanghai);
+        assert_eq!(shanghai.iter().map(|year| (year.year, year.count)).collect::<Vec<_>>(), [(2025, 2), (2024, 1)]);
+        assert_eq!(months(&shanghai), ["2025-01", "2024-11"]);
+
+        let utc = group_by_month(&articles, UTC);
+        assert_eq!(months(&utc), ["2025-01", "2024-12", "2024-11"]);
+    }
+}
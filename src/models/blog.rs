@@ -1,21 +1,78 @@
-use std::sync::Arc;
+use std::{cmp::Ordering, collections::{HashMap, HashSet}, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use crate::utils::*;
 
+/// 文章。列表接口只返回 `ArticleInfo`，正文和评论在打开文章时才按需加载，
+/// 因此 `content`/`comments` 为 `None` 时表示文章处于“仅摘要”状态。
 #[derive(Debug, Clone, PartialEq)]
 pub struct Article {
     info: Arc<ArticleInfo>,
-    content: String,
-    comments: Arc<Vec<Comment>>
+    content: Option<Arc<str>>,
+    comments: Option<Arc<Vec<Comment>>>,
+    /// 详情从本地缓存还原，可以先行显示，但评论可能已经过期
+    stale: bool,
 }
 impl Article {
     pub fn new(info: ArticleInfo, content: &str, comments: Vec<Comment>) -> Self {
         Article {
             info: Arc::new(info),
-            content: String::from(content),
-            comments: Arc::new(comments),
+            content: Some(Arc::from(content)),
+            comments: Some(Arc::new(comments)),
+            stale: false,
+        }
+    }
+    /// 从本地缓存还原的文章，打开时仍需向后端重新验证
+    pub fn from_cache(info: ArticleInfo, content: &str, comments: Vec<Comment>) -> Self {
+        Article { stale: true, ..Article::new(info, content, comments) }
+    }
+    /// 仅包含摘要信息的文章
+    pub fn from_info(info: ArticleInfo) -> Self {
+        Article {
+            info: Arc::new(info),
+            content: None,
+            comments: None,
+            stale: false,
+        }
+    }
+    /// 正文和评论是否都已加载（可能来自缓存）
+    pub fn is_loaded(&self) -> bool {
+        self.content.is_some() && self.comments.is_some()
+    }
+    /// 是否需要向后端请求详情：尚未加载，或详情来自缓存
+    pub fn needs_details(&self) -> bool {
+        !self.is_loaded() || self.stale
+    }
+    /// 用已加载的详情升级文章，保留同一份 `ArticleInfo`
+    pub fn with_details(&self, content: &str, comments: Vec<Comment>) -> Self {
+        Article {
+            info: self.info.clone(),
+            content: Some(Arc::from(content)),
+            comments: Some(Arc::new(comments)),
+            stale: false,
+        }
+    }
+    /// 重新验证失败时继续使用缓存的详情，不再重复请求
+    pub fn revalidated(&self) -> Self {
+        Article { stale: false, ..self.clone() }
+    }
+    /// 替换文章信息并保留已加载的正文和评论
+    pub fn with_info(&self, info: ArticleInfo) -> Self {
+        Article {
+            info: Arc::new(info),
+            content: self.content.clone(),
+            comments: self.comments.clone(),
+            stale: self.stale,
+        }
+    }
+    /// 替换评论列表，用于发表/编辑/删除评论后的即时更新
+    pub fn with_comments(&self, comments: Vec<Comment>) -> Self {
+        Article {
+            info: self.info.clone(),
+            content: self.content.clone(),
+            comments: Some(Arc::new(comments)),
+            stale: self.stale,
         }
     }
     pub fn aid(&self) -> String {
@@ -30,11 +87,13 @@ impl Article {
     pub fn categories(&self) -> &[String] {
         &self.info.categories
     }
+    /// 正文，未加载时为空字符串
     pub fn content(&self) -> &str {
-        &self.content
+        self.content.as_deref().unwrap_or_default()
     }
+    /// 评论，未加载时为空
     pub fn comments(&self) -> &[Comment] {
-        &self.comments
+        self.comments.as_deref().map(Vec::as_slice).unwrap_or_default()
     }
     pub fn created_at(&self) -> String {
         format_date_cn(self.info.created_at)
@@ -47,6 +106,55 @@ impl Article {
     }
 }
 
+/// 文章列表的排序方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// 最新发布在前
+    #[default]
+    Newest,
+    Oldest,
+    /// 最近更新在前
+    Updated,
+    Title,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 4] = [SortOrder::Newest, SortOrder::Oldest, SortOrder::Updated, SortOrder::Title];
+
+    /// 用于 URL 参数
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Newest => "newest",
+            SortOrder::Oldest => "oldest",
+            SortOrder::Updated => "updated",
+            SortOrder::Title => "title",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|order| order.as_str() == value)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::Newest => "最新发布",
+            SortOrder::Oldest => "最早发布",
+            SortOrder::Updated => "最近更新",
+            SortOrder::Title => "标题",
+        }
+    }
+
+    pub fn compare(&self, a: &Article, b: &Article) -> Ordering {
+        let (a, b) = (a.info(), b.info());
+        match self {
+            SortOrder::Newest => b.created_at.cmp(&a.created_at),
+            SortOrder::Oldest => a.created_at.cmp(&b.created_at),
+            SortOrder::Updated => b.updated_at.cmp(&a.updated_at),
+            SortOrder::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ArticleInfo {
     pub aid: String,
@@ -59,7 +167,56 @@ pub struct ArticleInfo {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone)]
+/// 测试用的 `ArticleInfo` 构造方法
+#[cfg(test)]
+impl ArticleInfo {
+    /// 标题同 `aid`，创建和更新时间为 Unix 纪元，其余字段为空
+    pub(crate) fn sample(aid: &str) -> Self {
+        let epoch = DateTime::from_timestamp(0, 0).unwrap();
+        ArticleInfo {
+            aid: aid.to_string(),
+            title: aid.to_string(),
+            summary: String::new(),
+            secret: None,
+            tags: Vec::new(),
+            categories: Vec::new(),
+            created_at: epoch,
+            updated_at: epoch,
+        }
+    }
+    pub(crate) fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+    pub(crate) fn with_summary(mut self, summary: &str) -> Self {
+        self.summary = summary.to_string();
+        self
+    }
+    pub(crate) fn with_secret(mut self, secret: &str) -> Self {
+        self.secret = Some(secret.to_string());
+        self
+    }
+    pub(crate) fn with_tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        self
+    }
+    pub(crate) fn with_categories(mut self, categories: &[&str]) -> Self {
+        self.categories = categories.iter().map(|category| category.to_string()).collect();
+        self
+    }
+    /// 同时设置创建和更新时间（Unix 秒）
+    pub(crate) fn created(mut self, secs: i64) -> Self {
+        self.created_at = DateTime::from_timestamp(secs, 0).unwrap();
+        self.updated_at = self.created_at;
+        self
+    }
+    pub(crate) fn updated(mut self, secs: i64) -> Self {
+        self.updated_at = DateTime::from_timestamp(secs, 0).unwrap();
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleCreateRequest {
     pub title: String,
     pub tags: Vec<String>,
@@ -80,20 +237,114 @@ pub struct ArticleModifyRequest {
     pub secret: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Comment {
     pub cid: String,
     pub uid: String,
+    /// 被回复的评论 id，顶层评论为 `None`
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// 评论者的用户名，旧版后端不返回时回退为 uid
+    #[serde(default)]
+    pub username: Option<String>,
     pub content: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Comment {
+    pub fn author(&self) -> &str {
+        self.username.as_deref().unwrap_or(&self.uid)
+    }
+}
+
+/// 评论树中的一个节点
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommentNode {
+    pub comment: Comment,
+    /// 超过最大嵌套深度而被展平的回复，记录其实际回复的对象
+    pub reply_to: Option<String>,
+    pub replies: Vec<CommentNode>,
+}
+
+/// 按 `parent` 将评论组织成树，保持原有顺序。
+/// 嵌套超过 `max_depth` 层的回复会被展平到第 `max_depth` 层，
+/// 父评论已不存在的回复作为顶层评论展示。
+pub fn build_comment_tree(comments: &[Comment], max_depth: usize) -> Vec<CommentNode> {
+    let index: HashMap<&str, usize> = comments
+        .iter()
+        .enumerate()
+        .map(|(idx, comment)| (comment.cid.as_str(), idx))
+        .collect();
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (idx, comment) in comments.iter().enumerate() {
+        match comment.parent.as_deref().and_then(|parent| index.get(parent)) {
+            Some(&parent) if parent != idx => children.entry(parent).or_default().push(idx),
+            _ => roots.push(idx),
+        }
+    }
+
+    fn flatten(idx: usize, comments: &[Comment], children: &HashMap<usize, Vec<usize>>, visited: &mut HashSet<usize>, out: &mut Vec<CommentNode>) {
+        for &child in children.get(&idx).map(Vec::as_slice).unwrap_or_default() {
+            if !visited.insert(child) {
+                continue;
+            }
+            out.push(CommentNode {
+                comment: comments[child].clone(),
+                reply_to: Some(comments[idx].author().to_string()),
+                replies: Vec::new(),
+            });
+            flatten(child, comments, children, visited, out);
+        }
+    }
+
+    fn build(idx: usize, depth: usize, max_depth: usize, comments: &[Comment], children: &HashMap<usize, Vec<usize>>, visited: &mut HashSet<usize>) -> CommentNode {
+        let mut replies = Vec::new();
+        if depth + 1 < max_depth {
+            for &child in children.get(&idx).map(Vec::as_slice).unwrap_or_default() {
+                if visited.insert(child) {
+                    replies.push(build(child, depth + 1, max_depth, comments, children, visited));
+                }
+            }
+        } else {
+            flatten(idx, comments, children, visited, &mut replies);
+            // 直接回复无需标注回复对象
+            for reply in replies.iter_mut() {
+                if reply.comment.parent.as_deref() == Some(comments[idx].cid.as_str()) {
+                    reply.reply_to = None;
+                }
+            }
+        }
+        CommentNode {
+            comment: comments[idx].clone(),
+            reply_to: None,
+            replies,
+        }
+    }
+
+    let mut visited: HashSet<usize> = roots.iter().copied().collect();
+    let mut tree = roots
+        .iter()
+        .map(|&idx| build(idx, 0, max_depth.max(1), comments, &children, &mut visited))
+        .collect::<Vec<_>>();
+    // 互相引用成环的评论没有根，兜底作为顶层评论
+    for idx in 0..comments.len() {
+        if visited.insert(idx) {
+            tree.push(build(idx, 0, max_depth.max(1), comments, &children, &mut visited));
+        }
+    }
+    tree
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommentCreateRequest {
     pub aid: String,
     pub uid: String,
     pub content: String,
+    /// 回复的评论 id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,4 +363,115 @@ pub struct Tag {
 pub struct Category {
     pub name: String,
     pub count: i32,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(cid: &str, parent: Option<&str>) -> Comment {
+        Comment {
+            cid: cid.to_string(),
+            uid: format!("user-{cid}"),
+            parent: parent.map(str::to_string),
+            username: None,
+            content: String::new(),
+            created_at: DateTime::from_timestamp(0, 0).unwrap(),
+            updated_at: DateTime::from_timestamp(0, 0).unwrap(),
+        }
+    }
+
+    /// 将评论树展开为 `(cid, 层级, reply_to)`，便于断言
+    fn shape(tree: &[CommentNode]) -> Vec<(String, usize, Option<String>)> {
+        fn walk(nodes: &[CommentNode], depth: usize, out: &mut Vec<(String, usize, Option<String>)>) {
+            for node in nodes {
+                out.push((node.comment.cid.clone(), depth, node.reply_to.clone()));
+                walk(&node.replies, depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(tree, 0, &mut out);
+        out
+    }
+
+    fn entry(cid: &str, depth: usize, reply_to: Option<&str>) -> (String, usize, Option<String>) {
+        (cid.to_string(), depth, reply_to.map(str::to_string))
+    }
+
+    #[test]
+    fn keeps_comment_order() {
+        let comments = [
+            comment("a", None),
+            comment("b", None),
+            comment("a2", Some("a")),
+            comment("b1", Some("b")),
+            comment("a1", Some("a")),
+        ];
+        assert_eq!(shape(&build_comment_tree(&comments, 4)), [
+            entry("a", 0, None),
+            entry("a2", 1, None),
+            entry("a1", 1, None),
+            entry("b", 0, None),
+            entry("b1", 1, None),
+        ]);
+    }
+
+    #[test]
+    fn flattens_replies_beyond_max_depth() {
+        let comments = [
+            comment("a", None),
+            comment("b", Some("a")),
+            comment("c", Some("b")),
+            comment("d", Some("c")),
+            comment("e", Some("b")),
+        ];
+        assert_eq!(shape(&build_comment_tree(&comments, 2)), [
+            entry("a", 0, None),
+            entry("b", 1, None),
+            entry("c", 2, None),
+            entry("d", 2, Some("user-c")),
+            entry("e", 2, None),
+        ]);
+        // 最大深度至少为 1，所有回复都展平到顶层评论之下
+        assert_eq!(shape(&build_comment_tree(&comments, 0)), [
+            entry("a", 0, None),
+            entry("b", 1, None),
+            entry("c", 1, Some("user-b")),
+            entry("d", 1, Some("user-c")),
+            entry("e", 1, Some("user-b")),
+        ]);
+    }
+
+    #[test]
+    fn promotes_orphans_and_cycles_to_top_level() {
+        let comments = [
+            comment("a", None),
+            comment("orphan", Some("deleted")),
+            comment("reply", Some("orphan")),
+            comment("x", Some("y")),
+            comment("y", Some("x")),
+            comment("self", Some("self")),
+        ];
+        assert_eq!(shape(&build_comment_tree(&comments, 4)), [
+            entry("a", 0, None),
+            entry("orphan", 0, None),
+            entry("reply", 1, None),
+            entry("self", 0, None),
+            entry("x", 0, None),
+            entry("y", 1, None),
+        ]);
+    }
+
+    #[test]
+    fn cached_article_still_needs_details() {
+        let info = ArticleInfo::sample("a");
+        assert!(Article::from_info(info.clone()).needs_details());
+        assert!(!Article::new(info.clone(), "body", Vec::new()).needs_details());
+
+        let cached = Article::from_cache(info, "body", Vec::new());
+        assert!(cached.is_loaded());
+        assert!(cached.needs_details());
+        assert!(cached.with_comments(Vec::new()).needs_details());
+        assert!(!cached.with_details("body", Vec::new()).needs_details());
+        assert!(!cached.revalidated().needs_details());
+    }
+}
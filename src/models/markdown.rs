@@ -1,19 +1,43 @@
 use serde::{Deserialize, Serialize};
 
 // 标题结构
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Heading {
     pub id: String,
     pub text: String,
     pub level: u32,
 }
 
+// 目录树节点
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocNode {
+    pub heading: Heading,
+    pub children: Vec<TocNode>,
+}
+
+// 按标题级别把扁平的标题列表组织成树，跳级的标题（如 h1 后直接 h3）挂在最近的上级下
+pub fn build_toc_tree(headings: &[Heading]) -> Vec<TocNode> {
+    fn insert(nodes: &mut Vec<TocNode>, heading: &Heading) {
+        match nodes.last_mut() {
+            Some(last) if heading.level > last.heading.level => insert(&mut last.children, heading),
+            _ => nodes.push(TocNode { heading: heading.clone(), children: Vec::new() }),
+        }
+    }
+    let mut tree = Vec::new();
+    for heading in headings {
+        insert(&mut tree, heading);
+    }
+    tree
+}
+
 // Front Matter 结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrontMatter {
     pub title: String,
     pub date: String,
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
 }
 
 // Markdown 内容结构
@@ -1,4 +1,5 @@
 pub mod blog;
+pub mod archive;
 pub mod user;
 pub mod response;
 pub mod markdown;
\ No newline at end of file
@@ -1,6 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct User {
     pub uid: String,
     pub username: String,
@@ -13,4 +14,35 @@ pub struct UserCreateRequest {
     pub username: String,
     pub password: String,
     pub email: Option<String>,
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// 后端签发的访问令牌
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AuthToken {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+}
+
+/// 登录成功后的会话，会持久化到 localStorage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub user: User,
+    pub token: AuthToken,
+}
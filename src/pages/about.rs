@@ -1,8 +1,11 @@
 use leptos::prelude::*;
 
+use crate::components::layout::meta::PageMeta;
+
 #[component]
 pub fn AboutPage() -> impl IntoView { 
     view! {
+        <PageMeta title="About" path="/about" />
         <h1>"About"</h1>
     }
 }
\ No newline at end of file
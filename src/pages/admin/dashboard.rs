@@ -0,0 +1,92 @@
+use leptos::{prelude::*, task::spawn_local};
+
+use crate::{
+    components::layout::meta::PrivatePageMeta,
+    api::blog::delete_article,
+    components::ui::button::{Button, Link},
+    state::use_app,
+    utils::format_date_cn,
+};
+
+#[component]
+pub fn AdminDashboard() -> impl IntoView {
+    let state = use_app();
+    let error = RwSignal::new(None::<String>);
+
+    let on_delete = {
+        let state = state.clone();
+        move |aid: String, title: String| {
+            let confirmed = web_sys::window()
+                .and_then(|window| window.confirm_with_message(&format!("确定删除《{title}》吗？")).ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            let state = state.clone();
+            spawn_local(async move {
+                match delete_article(&aid).await {
+                    Ok(()) => {
+                        error.set(None);
+                        state.load_data().await;
+                    }
+                    Err(err) => error.set(Some(err.to_string())),
+                }
+            });
+        }
+    };
+
+    view! {
+        <PrivatePageMeta title="文章管理" />
+        <div class="flex flex-col gap-6 mx-[10%]">
+            <div class="flex flex-row items-center justify-between">
+                <h1 class="text-3xl font-bold">"文章管理"</h1>
+                <Link href="/admin/new".to_string() class="bg-blue-500 hover:bg-blue-700 text-white font-bold px-4 py-2 rounded">
+                    "新建文章"
+                </Link>
+            </div>
+            <Show when=move || error.with(Option::is_some)>
+                <p class="text-red-500 text-sm" role="alert">{move || error.get().unwrap_or_default()}</p>
+            </Show>
+            <table class="w-full bg-white rounded-md shadow-md text-left">
+                <thead class="text-gray-500 text-sm border-b border-gray-200">
+                    <tr>
+                        <th class="p-4">"标题"</th>
+                        <th class="p-4">"创建时间"</th>
+                        <th class="p-4">"更新时间"</th>
+                        <th class="p-4"></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <For
+                        each=move || state.articles.get()
+                        key=|article| (article.aid(), article.info().updated_at)
+                        children=move |article| {
+                            let info = article.info().clone();
+                            let edit_url = format!("/admin/edit/{}", info.aid);
+                            let on_delete = on_delete.clone();
+                            let (aid, title) = (info.aid.clone(), info.title.clone());
+                            view! {
+                                <tr class="border-b border-gray-100">
+                                    <td class="p-4">
+                                        <Link href=format!("/blog/{}", info.aid)>{info.title.clone()}</Link>
+                                    </td>
+                                    <td class="p-4 text-sm text-gray-500">{format_date_cn(info.created_at)}</td>
+                                    <td class="p-4 text-sm text-gray-500">{format_date_cn(info.updated_at)}</td>
+                                    <td class="p-4 flex gap-4 justify-end">
+                                        <Link href=edit_url>"编辑"</Link>
+                                        <Button
+                                            class="text-red-500 hover:text-red-700"
+                                            on_click=Callback::new(move |_| on_delete(aid.clone(), title.clone()))
+                                        >
+                                            "删除"
+                                        </Button>
+                                    </td>
+                                </tr>
+                            }
+                        }
+                    />
+                </tbody>
+            </table>
+        </div>
+    }
+}
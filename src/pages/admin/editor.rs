@@ -0,0 +1,180 @@
+use leptos::{ev, prelude::*, task::spawn_local};
+use leptos_router::hooks::{use_navigate, use_params_map};
+
+use crate::{
+    components::layout::meta::PrivatePageMeta,
+    api::blog::{create_article, modify_article},
+    components::ui::{
+        button::Link,
+        form::{validate_required, FieldErrors, FormError, FormField, SubmitButton, TextAreaField},
+    },
+    models::blog::{ArticleCreateRequest, ArticleModifyRequest},
+    state::use_app,
+    utils::parse_markdown,
+};
+
+/// 将逗号分隔的输入拆分为列表，同时接受中英文逗号
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split([',', '，'])
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// 新建（`/admin/new`）和编辑（`/admin/edit/:id`）文章
+#[component]
+pub fn ArticleEditor() -> impl IntoView {
+    let state = use_app();
+    let params = use_params_map();
+    let navigate = use_navigate();
+    let aid = params.with_untracked(|params| params.get("id"));
+    let is_edit = aid.is_some();
+
+    let title = RwSignal::new(String::new());
+    let summary = RwSignal::new(String::new());
+    let tags = RwSignal::new(String::new());
+    let categories = RwSignal::new(String::new());
+    let secret = RwSignal::new(String::new());
+    let content = RwSignal::new(String::new());
+    let field_errors = RwSignal::new(FieldErrors::default());
+    let form_error = RwSignal::new(None::<String>);
+    let pending = RwSignal::new(false);
+    let populated = RwSignal::new(!is_edit);
+
+    // 编辑模式：等文章列表就绪后加载正文，并只填充一次表单
+    if let Some(aid) = aid.clone() {
+        let article = state.get_article(aid.clone());
+        let state = state.clone();
+        Effect::new(move |_| {
+            if state.loading.get() || populated.get() {
+                return;
+            }
+            match article.get() {
+                Some(article) if article.is_loaded() => {
+                    let info = article.info();
+                    title.set(info.title.clone());
+                    summary.set(info.summary.clone());
+                    tags.set(info.tags.join(", "));
+                    categories.set(info.categories.join(", "));
+                    secret.set(info.secret.clone().unwrap_or_default());
+                    content.set(article.content().to_string());
+                    populated.set(true);
+                }
+                // 详情正在后台预取时等待其结束，失败后会重新请求
+                Some(_) if form_error.with(Option::is_some) || state.is_details_pending(&aid) => {}
+                Some(_) => {
+                    let state = state.clone();
+                    let aid = aid.clone();
+                    spawn_local(async move {
+                        if let Err(err) = state.load_article_details(&aid).await {
+                            form_error.set(Some(err.to_string()));
+                        }
+                    });
+                }
+                None => form_error.set(Some(format!("文章 {aid} 不存在"))),
+            }
+        });
+    }
+
+    let preview = Memo::new(move |_| {
+        content.with(|content| parse_markdown(content).map(|md| md.html_content).unwrap_or_default())
+    });
+
+    let on_submit = {
+        let state = state.clone();
+        move |ev: ev::SubmitEvent| {
+            ev.prevent_default();
+            if pending.get_untracked() || !populated.get_untracked() {
+                return;
+            }
+            let mut errors = FieldErrors::default();
+            errors.check("title", validate_required(&title.get_untracked(), "标题"));
+            errors.check("content", validate_required(&content.get_untracked(), "正文"));
+            let valid = errors.is_empty();
+            field_errors.set(errors);
+            form_error.set(None);
+            if !valid {
+                return;
+            }
+
+            let state = state.clone();
+            let navigate = navigate.clone();
+            let aid = aid.clone();
+            pending.set(true);
+            spawn_local(async move {
+                let result = match aid {
+                    Some(aid) => modify_article(&ArticleModifyRequest {
+                        aid,
+                        title: Some(title.get_untracked().trim().to_string()),
+                        tags: Some(split_list(&tags.get_untracked())),
+                        categories: Some(split_list(&categories.get_untracked())),
+                        summary: Some(summary.get_untracked().trim().to_string()),
+                        content: Some(content.get_untracked()),
+                        secret: non_empty(secret.get_untracked()),
+                    }).await,
+                    None => create_article(&ArticleCreateRequest {
+                        title: title.get_untracked().trim().to_string(),
+                        tags: split_list(&tags.get_untracked()),
+                        categories: split_list(&categories.get_untracked()),
+                        summary: summary.get_untracked().trim().to_string(),
+                        content: content.get_untracked(),
+                        secret: non_empty(secret.get_untracked()),
+                    }).await,
+                };
+                match result {
+                    Ok(_) => {
+                        state.load_data().await;
+                        navigate("/admin", Default::default());
+                    }
+                    Err(err) => {
+                        let (errors, message) = FieldErrors::from_api_error(&err, &[]);
+                        field_errors.set(errors);
+                        form_error.set(message);
+                    }
+                }
+                pending.set(false);
+            });
+        }
+    };
+
+    let error_of = move |field: &'static str| Signal::derive(move || field_errors.with(|e| e.get(field)));
+
+    view! {
+        <PrivatePageMeta title=if is_edit { "编辑文章" } else { "新建文章" } />
+        <div class="flex flex-col gap-6 mx-[5%]">
+            <div class="flex flex-row items-center justify-between">
+                <h1 class="text-3xl font-bold">{if is_edit { "编辑文章" } else { "新建文章" }}</h1>
+                <Link href="/admin".to_string()>"返回"</Link>
+            </div>
+            <form class="flex flex-col gap-4 bg-white rounded-md shadow-md p-6" on:submit=on_submit novalidate>
+                <FormError error=form_error />
+                <FormField name="title" label="标题" value=title error=error_of("title") />
+                <FormField name="summary" label="摘要" value=summary error=error_of("summary") />
+                <div class="grid grid-cols-3 gap-4">
+                    <FormField name="tags" label="标签（逗号分隔）" value=tags />
+                    <FormField name="categories" label="分类（逗号分隔）" value=categories />
+                    <FormField name="secret" label="访问密码（可选）" value=secret />
+                </div>
+                <div class="grid grid-cols-2 gap-4">
+                    <TextAreaField name="content" label="正文（Markdown）" value=content class="h-[60vh]" error=error_of("content") />
+                    <div class="flex flex-col gap-1">
+                        <span class="text-sm text-gray-700">"预览"</span>
+                        <div class="h-[60vh] overflow-y-auto rounded-md border border-gray-200 px-4 py-2" inner_html=move || preview.get()></div>
+                    </div>
+                </div>
+                <div class="w-48 self-end">
+                    <SubmitButton pending=pending>
+                        {move || if pending.get() { "保存中..." } else { "保存" }}
+                    </SubmitButton>
+                </div>
+            </form>
+        </div>
+    }
+}
@@ -0,0 +1,98 @@
+use chrono_tz::Tz;
+use leptos::prelude::*;
+
+use crate::{
+    components::{layout::meta::PageMeta, ui::button::Link},
+    models::archive::{group_by_month, ArchiveYear},
+    pages::{blog::query::BlogQuery, error::LoadErrorPage, loading::LoadingPage},
+    state::use_app,
+};
+
+/// 可供选择的时区
+const TIMEZONES: [Tz; 7] = [
+    Tz::Asia__Shanghai,
+    Tz::Asia__Tokyo,
+    Tz::Asia__Singapore,
+    Tz::Europe__London,
+    Tz::America__New_York,
+    Tz::America__Los_Angeles,
+    Tz::UTC,
+];
+
+#[component]
+pub fn ArchivePage() -> impl IntoView {
+    let state = use_app();
+    let timezone = state.timezone;
+    let archive = Memo::new(move |_| {
+        let timezone = timezone.get();
+        state.articles.with(|articles| group_by_month(articles.iter().map(|article| article.info()), timezone))
+    });
+
+    let on_timezone_change = {
+        let state = state.clone();
+        move |ev| {
+            if let Ok(timezone) = event_target_value(&ev).parse() {
+                state.set_timezone(timezone);
+            }
+        }
+    };
+
+    view! {
+        <PageMeta title="Archive" path="/archive" description="按年月浏览全部文章" />
+        <Show when=move || state.error.get().is_none()
+            fallback=move || view! { <LoadErrorPage /> }
+        >
+            <div class="flex flex-col mx-[10%] gap-8">
+                <div class="flex flex-wrap items-center justify-between gap-4">
+                    <h1 class="text-2xl font-bold">"Archive"</h1>
+                    <label class="flex items-center gap-2 text-sm text-gray-500">
+                        "时区"
+                        <select class="rounded border border-gray-300 px-2 py-1" on:change=on_timezone_change.clone()>
+                            {TIMEZONES.into_iter().map(|tz| view! {
+                                <option value=tz.name() selected=move || timezone.get() == tz>{tz.name()}</option>
+                            }).collect_view()}
+                        </select>
+                    </label>
+                </div>
+                <Show when=move || !state.loading.get() fallback=|| view! { <LoadingPage /> }>
+                    <Show when=move || !archive.with(Vec::is_empty) fallback=|| view! { <p class="text-gray-500">"还没有文章"</p> }>
+                        {move || archive.get().into_iter().map(|year| view! { <ArchiveYearSection year /> }).collect_view()}
+                    </Show>
+                </Show>
+            </div>
+        </Show>
+    }
+}
+
+/// 一年的时间线，默认展开，可以折叠
+#[component]
+fn ArchiveYearSection(year: ArchiveYear) -> impl IntoView {
+    view! {
+        <details open class="rounded-lg bg-white p-4 shadow dark:bg-gray-800">
+            <summary class="cursor-pointer text-xl font-bold">
+                {format!("{} 年", year.year)}
+                <span class="ml-2 text-sm font-normal text-gray-500">{format!("{} 篇", year.count)}</span>
+            </summary>
+            <ol class="mt-4 ml-2 border-l-2 border-gray-200 pl-6">
+                {year.months.into_iter().map(|month| {
+                    let url = BlogQuery { month: Some(month.month), ..Default::default() }.to_url();
+                    view! {
+                        <li class="mb-6">
+                            <h2 class="text-lg font-semibold">
+                                <Link href=url>{format!("{} 月", month.month.month)}</Link>
+                                <span class="ml-2 text-sm font-normal text-gray-500">{format!("{} 篇", month.articles.len())}</span>
+                            </h2>
+                            <ul class="mt-2 flex flex-col gap-1">
+                                {month.articles.into_iter().map(|info| view! {
+                                    <li>
+                                        <Link href=format!("/blog/{}", info.aid)>{info.title}</Link>
+                                    </li>
+                                }).collect_view()}
+                            </ul>
+                        </li>
+                    }
+                }).collect_view()}
+            </ol>
+        </details>
+    }
+}
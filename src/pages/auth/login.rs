@@ -0,0 +1,115 @@
+use leptos::{ev, prelude::*, task::spawn_local};
+use leptos_router::{hooks::{use_navigate, use_query_map}, location::Url};
+
+use crate::{
+    components::layout::meta::PrivatePageMeta,
+    components::ui::{button::Link, form::{validate_required, FieldErrors, FormError, FormField, InputKind, SubmitButton}},
+    models::user::LoginRequest,
+    state::use_auth,
+};
+
+/// 登录成功后跳转的地址，只接受站内路径
+pub fn redirect_target(redirect: Option<String>) -> String {
+    redirect
+        .filter(|path| path.starts_with('/') && !path.starts_with("//"))
+        .unwrap_or_else(|| "/".to_string())
+}
+
+/// 登录页地址，登录成功后回到 `path`
+pub fn login_path(path: &str) -> String {
+    format!("/login?redirect={}", Url::escape(path))
+}
+
+#[component]
+pub fn LoginPage() -> impl IntoView {
+    let auth = use_auth();
+    let query = use_query_map();
+    let navigate = use_navigate();
+
+    let username = RwSignal::new(String::new());
+    let password = RwSignal::new(String::new());
+    let field_errors = RwSignal::new(FieldErrors::default());
+    let form_error = RwSignal::new(None::<String>);
+    let pending = RwSignal::new(false);
+
+    let on_submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        if pending.get_untracked() {
+            return;
+        }
+        let mut errors = FieldErrors::default();
+        errors.check("username", validate_required(&username.get_untracked(), "用户名"));
+        errors.check("password", validate_required(&password.get_untracked(), "密码"));
+        let valid = errors.is_empty();
+        field_errors.set(errors);
+        form_error.set(None);
+        if !valid {
+            return;
+        }
+
+        let request = LoginRequest {
+            username: username.get_untracked().trim().to_string(),
+            password: password.get_untracked(),
+        };
+        let redirect = redirect_target(query.with_untracked(|q| q.get("redirect")));
+        let navigate = navigate.clone();
+        pending.set(true);
+        spawn_local(async move {
+            match auth.login(request).await {
+                Ok(_) => navigate(&redirect, Default::default()),
+                Err(err) => {
+                    let (errors, message) = FieldErrors::from_api_error(&err, &[]);
+                    field_errors.set(errors);
+                    form_error.set(message);
+                }
+            }
+            pending.set(false);
+        });
+    };
+
+    view! {
+        <PrivatePageMeta title="登录" />
+        <AuthCard title="登录">
+            <form class="flex flex-col gap-4" on:submit=on_submit novalidate>
+                <FormError error=form_error />
+                <FormField
+                    name="username"
+                    label="用户名"
+                    value=username
+                    autocomplete="username"
+                    error=Signal::derive(move || field_errors.with(|e| e.get("username")))
+                />
+                <FormField
+                    name="password"
+                    label="密码"
+                    kind=InputKind::Password
+                    value=password
+                    autocomplete="current-password"
+                    error=Signal::derive(move || field_errors.with(|e| e.get("password")))
+                />
+                <SubmitButton pending=pending>
+                    {move || if pending.get() { "登录中..." } else { "登录" }}
+                </SubmitButton>
+            </form>
+            <p class="text-sm text-gray-600 text-center">
+                "还没有账号？"
+                <Link href="/register".to_string() class="text-blue-500 hover:underline">"注册"</Link>
+            </p>
+        </AuthCard>
+    }
+}
+
+#[component]
+pub fn AuthCard(
+    title: &'static str,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <div class="flex justify-center">
+            <div class="flex flex-col gap-6 w-full max-w-sm p-8 bg-white rounded-md shadow-md">
+                <h1 class="text-2xl font-bold text-center">{title}</h1>
+                {children()}
+            </div>
+        </div>
+    }
+}
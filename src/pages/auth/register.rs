@@ -0,0 +1,90 @@
+use leptos::{ev, prelude::*, task::spawn_local};
+use leptos_router::hooks::use_navigate;
+
+use crate::{
+    components::layout::meta::PrivatePageMeta,
+    components::ui::{button::Link, form::{validate_email, validate_length, FieldErrors, FormError, FormField, InputKind, SubmitButton}},
+    models::user::UserCreateRequest,
+    pages::auth::login::AuthCard,
+    state::use_auth,
+};
+
+/// 用户名已被占用时后端返回的错误码（409 Conflict）
+const USERNAME_TAKEN: u16 = 409;
+
+#[component]
+pub fn RegisterPage() -> impl IntoView {
+    let auth = use_auth();
+    let navigate = use_navigate();
+
+    let username = RwSignal::new(String::new());
+    let email = RwSignal::new(String::new());
+    let password = RwSignal::new(String::new());
+    let confirm = RwSignal::new(String::new());
+    let field_errors = RwSignal::new(FieldErrors::default());
+    let form_error = RwSignal::new(None::<String>);
+    let pending = RwSignal::new(false);
+
+    let on_submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        if pending.get_untracked() {
+            return;
+        }
+        let mut errors = FieldErrors::default();
+        errors.check("username", validate_length(username.get_untracked().trim(), "用户名", 3, 32));
+        errors.check("email", validate_email(&email.get_untracked()));
+        if errors.check("password", validate_length(&password.get_untracked(), "密码", 8, 64))
+            && password.get_untracked() != confirm.get_untracked()
+        {
+            errors.set("confirm", "两次输入的密码不一致");
+        }
+        let valid = errors.is_empty();
+        field_errors.set(errors);
+        form_error.set(None);
+        if !valid {
+            return;
+        }
+
+        let email = email.get_untracked().trim().to_string();
+        let request = UserCreateRequest {
+            username: username.get_untracked().trim().to_string(),
+            password: password.get_untracked(),
+            email: (!email.is_empty()).then_some(email),
+        };
+        let navigate = navigate.clone();
+        pending.set(true);
+        spawn_local(async move {
+            match auth.register(request).await {
+                Ok(_) => navigate("/", Default::default()),
+                Err(err) => {
+                    let (errors, message) = FieldErrors::from_api_error(&err, &[(USERNAME_TAKEN, "username")]);
+                    field_errors.set(errors);
+                    form_error.set(message);
+                }
+            }
+            pending.set(false);
+        });
+    };
+
+    let error_of = move |field: &'static str| Signal::derive(move || field_errors.with(|e| e.get(field)));
+
+    view! {
+        <PrivatePageMeta title="注册" />
+        <AuthCard title="注册">
+            <form class="flex flex-col gap-4" on:submit=on_submit novalidate>
+                <FormError error=form_error />
+                <FormField name="username" label="用户名" value=username autocomplete="username" error=error_of("username") />
+                <FormField name="email" label="邮箱（可选）" kind=InputKind::Email value=email autocomplete="email" error=error_of("email") />
+                <FormField name="password" label="密码" kind=InputKind::Password value=password autocomplete="new-password" error=error_of("password") />
+                <FormField name="confirm" label="确认密码" kind=InputKind::Password value=confirm autocomplete="new-password" error=error_of("confirm") />
+                <SubmitButton pending=pending>
+                    {move || if pending.get() { "注册中..." } else { "注册" }}
+                </SubmitButton>
+            </form>
+            <p class="text-sm text-gray-600 text-center">
+                "已有账号？"
+                <Link href="/login".to_string() class="text-blue-500 hover:underline">"登录"</Link>
+            </p>
+        </AuthCard>
+    }
+}
@@ -1,9 +1,9 @@
 use std::cmp::{max, min};
 
-use leptos::prelude::*;
+use leptos::{prelude::*, task::spawn_local};
 use leptos_router::hooks::use_params_map;
 
-use crate::{components::ui::{button::Button, card::{ArticleCard, ArticleInfoCard, FilterBarCard}}, pages::{loading::LoadingPage, notfound::NotFoundPage}, state::use_app};
+use crate::{api::error::ApiError, models::blog::Article, components::ui::{button::Button, card::{ArticleCard, ArticleInfoCard, FilterBarCard}}, pages::{error::{ErrorPage, LoadErrorPage}, loading::LoadingPage, notfound::NotFoundPage}, state::use_app};
 
 #[component]
 pub fn ArticleList() -> impl IntoView {
@@ -11,9 +11,9 @@ pub fn ArticleList() -> impl IntoView {
     let current_page = state.current_page;
     let items_per_page = state.items_per_page;
     let article_list = state.filtered_results;
-    let total_pages = Memo::new(move |_| {
-        let articles = state.articles.get();
-        articles.len() / items_per_page.get() + (articles.len() % items_per_page.get() > 0) as usize + (articles.len() == 0) as usize
+    let total_pages = Memo::new({
+        let state = state.clone();
+        move |_| state.total_pages()
     });
     Effect::new(move |_| {
         // 当 current_page 变化时执行
@@ -67,7 +67,7 @@ fn PageLeader(
 
     view! {
         <div>
-            <Show when=move || (total_pages.get() > 1)>
+            <Show when=move || { total_pages.get() > 1 }>
                 <div class="flex justify-center gap-4">
                     <Button
                         class="bg-blue-500 hover:bg-blue-700 text-white font-bold p-2 rounded"
@@ -81,10 +81,7 @@ fn PageLeader(
                         each=move || {page_range.get()}
                         key=|page| { *page }
                         children=move |page| {
-                            let is_current = Memo::new({
-                                let page = page;
-                                move |_| page == state.current_page.get()
-                            });
+                            let is_current = Memo::new(move |_| page == state.current_page.get());
                             
                             let button_class = Memo::new(move |_| {
                                 let bg_color = if is_current.get() { "bg-blue-200" } else { "bg-white" };
@@ -122,16 +119,54 @@ pub fn ArticleDital() -> impl IntoView {
     let id = Memo::new(move |_| params.with(|params| params.get("id").unwrap_or_default()));
     let state = use_app();
     let article = state.get_article(id.get());
+    let detail_error = RwSignal::new(None::<ApiError>);
+    let loaded = Memo::new(move |_| article.with(|a| a.as_ref().is_some_and(Article::is_loaded)));
+    // 缓存还原的详情先行显示，同时仍向后端重新验证评论
+    let needs_details = Memo::new(move |_| article.with(|a| a.as_ref().is_some_and(Article::needs_details)));
+    // 列表加载完成后按需拉取正文和评论；重试时清空 detail_error 会重新触发。
+    // 详情正在后台预取时等待其结束，预取失败后文章仍未加载会在这里重新请求
+    Effect::new({
+        let state = state.clone();
+        move |_| {
+            if state.loading.get() || detail_error.with(Option::is_some) || !needs_details.get() {
+                return;
+            }
+            let aid = id.get_untracked();
+            if state.is_details_pending(&aid) {
+                return;
+            }
+            let state = state.clone();
+            spawn_local(async move {
+                if let Err(err) = state.load_article_details(&aid).await {
+                    detail_error.set(Some(err));
+                }
+            });
+        }
+    });
     view! {
         <Show when=move || !state.loading.get()
             fallback=move || view! { <LoadingPage /> }
         >
+        <Show when=move || state.error.get().is_none()
+            fallback=move || view! { <LoadErrorPage /> }
+        >
         <Show when=move || article.get().is_some()
             fallback=move || view! { <NotFoundPage/> }
+        >
+        <Show when=move || detail_error.with(Option::is_none)
+            fallback=move || detail_error.get().map(|error| view! {
+                <ErrorPage error=error on_retry=move || detail_error.set(None) />
+            })
+        >
+        <Show when=move || loaded.get()
+            fallback=move || view! { <LoadingPage /> }
         >
             <ArticleCard article=article/>
         </Show>
         </Show>
+        </Show>
+        </Show>
+        </Show>
     }
 }
 
@@ -144,6 +179,10 @@ pub fn FilterBar() -> impl IntoView {
             categories=state.categories
             selected_tags=state.selected_tags
             selected_categories=state.selected_categories
+            filter_mode=state.filter_mode
+            sort_order=state.sort_order
+            selected_month=state.selected_month
+            result_count=Signal::derive(move || state.filtered_results.with(Vec::len))
         />
     }
 }
\ No newline at end of file
@@ -1,11 +1,17 @@
 use leptos::prelude::*;
 
-use crate::pages::blog::article::ArticleList;
+use crate::{components::layout::meta::PageMeta, pages::{blog::{article::ArticleList, query::sync_blog_query}, error::LoadErrorPage}, state::use_app};
 
 #[component]
 pub fn BlogIndex() -> impl IntoView {
+    let state = use_app();
+    sync_blog_query(&state);
     view! {
-        <ArticleList/>
-
+        <PageMeta title="Blog" path="/blog" />
+        <Show when=move || state.error.get().is_none()
+            fallback=move || view! { <LoadErrorPage /> }
+        >
+            <ArticleList/>
+        </Show>
     }
-}
\ No newline at end of file
+}
@@ -1,2 +1,3 @@
 pub mod index;
-pub mod article;
\ No newline at end of file
+pub mod article;
+pub mod query;
@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+use leptos::prelude::*;
+use leptos_router::{
+    hooks::{use_navigate, use_query_map},
+    params::ParamsMap,
+    NavigateOptions,
+};
+
+use crate::{models::{archive::YearMonth, blog::SortOrder}, search::query::FilterMode, state::AppState};
+
+const BLOG_PATH: &str = "/blog";
+
+/// `/blog` 的查询参数：`?tag=a&tag=b&category=c&mode=all&month=2025-03&sort=oldest&page=3`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlogQuery {
+    pub tags: BTreeSet<String>,
+    pub categories: BTreeSet<String>,
+    pub mode: FilterMode,
+    pub month: Option<YearMonth>,
+    pub sort: SortOrder,
+    pub page: usize,
+}
+
+impl BlogQuery {
+    pub fn from_params(params: &ParamsMap) -> Self {
+        let values = |key: &str| params.get_all(key).unwrap_or_default().into_iter().filter(|v| !v.is_empty()).collect();
+        Self {
+            tags: values("tag"),
+            categories: values("category"),
+            mode: params.get_str("mode").and_then(FilterMode::parse).unwrap_or_default(),
+            month: params.get_str("month").and_then(|month| month.parse().ok()),
+            sort: params.get_str("sort").and_then(SortOrder::parse).unwrap_or_default(),
+            page: params.get_str("page").and_then(|page| page.parse().ok()).filter(|page| *page > 0).unwrap_or(1),
+        }
+    }
+
+    fn from_state(state: &AppState) -> Self {
+        Self {
+            tags: state.selected_tags.with(|tags| tags.iter().cloned().collect()),
+            categories: state.selected_categories.with(|categories| categories.iter().cloned().collect()),
+            mode: state.filter_mode.get(),
+            month: state.selected_month.get(),
+            sort: state.sort_order.get(),
+            page: state.current_page.get(),
+        }
+    }
+
+    fn apply(&self, state: &AppState) {
+        state.selected_tags.set(self.tags.iter().cloned().collect());
+        state.selected_categories.set(self.categories.iter().cloned().collect());
+        state.filter_mode.set(self.mode);
+        state.selected_month.set(self.month);
+        state.sort_order.set(self.sort);
+        state.current_page.set(self.page);
+    }
+
+    /// 生成链接，参数顺序固定，取默认值的参数省略
+    pub fn to_url(&self) -> String {
+        let mut params = ParamsMap::new();
+        for tag in &self.tags {
+            params.insert("tag", tag.clone());
+        }
+        for category in &self.categories {
+            params.insert("category", category.clone());
+        }
+        if self.mode != FilterMode::default() {
+            params.insert("mode", self.mode.as_str().to_string());
+        }
+        if let Some(month) = self.month {
+            params.insert("month", month.to_string());
+        }
+        if self.sort != SortOrder::default() {
+            params.insert("sort", self.sort.as_str().to_string());
+        }
+        if self.page > 1 {
+            params.insert("page", self.page.to_string());
+        }
+        format!("{BLOG_PATH}{}", params.to_query_string())
+    }
+}
+
+/// 以 URL 为准，双向同步筛选条件和页码：
+/// 打开或前进后退时用 URL 覆盖 `AppState`，修改筛选或翻页时写回 URL。
+/// 只有读者的操作会新增历史记录；规范化 URL、修正越界页码这类派生改动
+/// 替换当前记录，避免返回键被困在同一页
+pub fn sync_blog_query(state: &AppState) {
+    let query = use_query_map();
+    let navigate = use_navigate();
+    // 状态刚由 URL 覆盖过，下一次写回只是规范化
+    let from_url_applied = StoredValue::new(false);
+
+    // 先用 URL 初始化，避免下面的写回把 URL 中的参数清掉
+    let initial = query.with_untracked(BlogQuery::from_params);
+    untrack(|| {
+        if initial != BlogQuery::from_state(state) {
+            initial.apply(state);
+        }
+    });
+    from_url_applied.set_value(true);
+
+    Effect::new({
+        let state = state.clone();
+        move |_| {
+            let from_url = query.with(BlogQuery::from_params);
+            if untrack(|| BlogQuery::from_state(&state)) != from_url {
+                from_url.apply(&state);
+                from_url_applied.set_value(true);
+            }
+        }
+    });
+
+    Effect::new({
+        let state = state.clone();
+        move |_| {
+            let mut next = BlogQuery::from_state(&state);
+            let current = query.with_untracked(BlogQuery::from_params);
+            let mut replace = from_url_applied.get_value();
+            from_url_applied.set_value(false);
+            // 筛选条件变化后当前页可能超出范围；加载完成前不调整，以免覆盖 URL 中的页码
+            let total = state.total_pages();
+            if !state.loading.get() && next.page > total {
+                replace |= next == current;
+                next.page = total;
+                state.current_page.set(total);
+            }
+            if current != next {
+                navigate(&next.to_url(), NavigateOptions { replace, ..Default::default() });
+            }
+        }
+    });
+}
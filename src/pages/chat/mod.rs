@@ -1,8 +1,11 @@
 use leptos::prelude::*;
 
+use crate::components::layout::meta::PageMeta;
+
 #[component]
 pub fn ChatGroupList() -> impl IntoView { 
     view! {
+        <PageMeta title="Chat" path="/chat" />
         <h1>"CHAT GROUP LIST"</h1>
     }
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use leptos::prelude::*;
+
+use crate::{api::error::ApiError, components::ui::button::Button, state::use_app};
+
+#[component]
+pub fn ErrorPage(
+    error: ApiError,
+    #[prop(optional, into)]
+    on_retry: Option<Callback<()>>,
+) -> impl IntoView {
+    let retryable = error.is_retryable();
+    view! {
+        <div class="flex flex-col justify-center items-center gap-4 h-[50vh]">
+            <h1 class="text-2xl font-bold">{error.title()}</h1>
+            <p class="text-gray-500 text-sm">{error.to_string()}</p>
+            {on_retry.filter(|_| retryable).map(|on_retry| view! {
+                <Button
+                    class="bg-blue-500 hover:bg-blue-700 text-white font-bold px-4 py-2 rounded"
+                    on_click=Callback::new(move |_| on_retry.run(()))
+                >
+                    "Retry"
+                </Button>
+            })}
+        </div>
+    }
+}
+
+/// 全局数据加载失败时的错误页，重试会重新调用 `AppState::load_data`
+#[component]
+pub fn LoadErrorPage() -> impl IntoView {
+    let state = use_app();
+    move || {
+        let state = state.clone();
+        state.error.get().map(|error| view! {
+            <ErrorPage error=error on_retry=move || state.reload() />
+        })
+    }
+}
@@ -1,8 +1,11 @@
 use leptos::prelude::*;
 
+use crate::{components::layout::meta::PageMeta, seo::SITE_NAME};
+
 #[component]
 pub fn HomePage() -> impl IntoView { 
     view! {
+        <PageMeta title=SITE_NAME path="/" />
         <div class="flex flex-col justify-center items-center h-[70vh]">
             <div id="title" class="text-6xl font-bold">
                 "Rusite"
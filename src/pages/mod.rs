@@ -1,7 +1,11 @@
 pub mod home;
+pub mod auth;
+pub mod admin;
 pub mod blog;
+pub mod archive;
 pub mod chat;
 pub mod user;
 pub mod about;
 pub mod notfound;
-pub mod loading;
\ No newline at end of file
+pub mod loading;
+pub mod error;
\ No newline at end of file
@@ -1,8 +1,11 @@
 use leptos::prelude::*;
 
+use crate::components::layout::meta::PrivatePageMeta;
+
 #[component]
 pub fn NotFoundPage() -> impl IntoView { 
     view! {
+        <PrivatePageMeta title="Not Found" />
         <h1>"Not Found."</h1>
     }
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
 use leptos::prelude::*;
 
+use crate::components::layout::meta::PrivatePageMeta;
+
 #[component]
 pub fn UserProfilePage() -> impl IntoView { 
     view! {
+        <PrivatePageMeta title="User" />
         <h1>"User"</h1>
     }
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+/// 匹配方式，精确匹配的权重最高
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    Exact,
+    /// 输入中的最后一个词尚未打完时按前缀匹配
+    Prefix,
+    /// 允许少量拼写错误
+    Fuzzy,
+}
+
+impl MatchKind {
+    pub fn weight(self) -> f64 {
+        match self {
+            MatchKind::Exact => 1.0,
+            MatchKind::Prefix => 0.6,
+            MatchKind::Fuzzy => 0.4,
+        }
+    }
+}
+
+/// 前缀匹配至少需要的字符数
+pub const MIN_PREFIX_LEN: usize = 2;
+
+/// 只对拉丁字母组成的词做前缀和模糊匹配，中日韩文字已按 n-gram 切分
+pub fn is_latin(term: &str) -> bool {
+    term.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// 按词长允许的最大编辑距离：短词不容错，避免“go”匹配到“do”
+pub fn max_typos(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal String Alignment 距离：插入、删除、替换以及相邻字符交换各算一次编辑
+pub fn osa_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let width = b.len() + 1;
+    let mut d = vec![0; (a.len() + 1) * width];
+    for i in 0..=a.len() {
+        d[i * width] = i;
+    }
+    for (j, cell) in d.iter_mut().enumerate().take(width) {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (d[(i - 1) * width + j] + 1)
+                .min(d[i * width + j - 1] + 1)
+                .min(d[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(d[(i - 2) * width + j - 2] + 1);
+            }
+            d[i * width + j] = best;
+        }
+    }
+    d[a.len() * width + b.len()]
+}
+
+/// 判断索引中的词 `candidate` 能否匹配查询词 `term`
+pub fn match_kind(term: &str, candidate: &str, prefix: bool) -> Option<MatchKind> {
+    if term == candidate {
+        return Some(MatchKind::Exact);
+    }
+    if !is_latin(term) {
+        return None;
+    }
+    if prefix && term.len() >= MIN_PREFIX_LEN && candidate.starts_with(term) {
+        return Some(MatchKind::Prefix);
+    }
+    let typos = max_typos(term);
+    if typos > 0 && term.len().abs_diff(candidate.len()) <= typos && osa_distance(term, candidate) <= typos {
+        return Some(MatchKind::Fuzzy);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_transpositions_as_one_edit() {
+        assert_eq!(osa_distance("leptso", "leptos"), 1);
+        assert_eq!(osa_distance("pulldwon", "pulldown"), 1);
+        assert_eq!(osa_distance("kitten", "sitting"), 3);
+        assert_eq!(osa_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn classifies_matches() {
+        assert_eq!(match_kind("rust", "rust", false), Some(MatchKind::Exact));
+        assert_eq!(match_kind("lep", "leptos", true), Some(MatchKind::Prefix));
+        assert_eq!(match_kind("lep", "leptos", false), None);
+        assert_eq!(match_kind("leptso", "leptos", false), Some(MatchKind::Fuzzy));
+        assert_eq!(match_kind("go", "do", false), None);
+        assert_eq!(match_kind("搜索", "搜素", false), None);
+    }
+}
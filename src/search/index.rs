@@ -0,0 +1,376 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use pulldown_cmark::{Event, Parser};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::blog::{Article, ArticleInfo},
+    search::{fuzzy::{match_kind, MatchKind}, tokenize::tokenize},
+};
+
+// BM25 参数
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// 参与检索的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Title,
+    Summary,
+    Tags,
+    Categories,
+    Content,
+}
+
+const FIELD_COUNT: usize = 5;
+
+impl Field {
+    pub const ALL: [Field; FIELD_COUNT] = [Field::Title, Field::Summary, Field::Tags, Field::Categories, Field::Content];
+
+    /// 字段权重，标题命中最重要
+    pub fn weight(self) -> f64 {
+        match self {
+            Field::Title => 5.0,
+            Field::Tags => 3.0,
+            Field::Categories => 2.0,
+            Field::Summary => 2.0,
+            Field::Content => 1.0,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+type TermFrequencies = [u32; FIELD_COUNT];
+
+/// 已建立索引的文章
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDoc {
+    /// 文章版本，`updated_at` 或正文加载状态变化时重建
+    fingerprint: (DateTime<Utc>, bool),
+    lengths: [usize; FIELD_COUNT],
+    terms: HashSet<String>,
+    summary: String,
+    /// 去掉 Markdown 标记后的正文，用于生成摘录
+    text: String,
+}
+
+/// 一次命中
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub aid: String,
+    pub score: f64,
+    /// 文章中实际命中的索引词（含前缀和模糊匹配到的词），用于高亮
+    pub terms: Vec<String>,
+}
+
+/// 倒排索引，按 BM25F 对标题、摘要、标签、分类和正文加权打分。
+/// 通过 `sync` 增量更新：只重建新增或版本变化的文章。
+/// 静态导出时预先生成并序列化，浏览器下载后无需逐篇加载正文即可全文检索。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    docs: HashMap<String, IndexedDoc>,
+    postings: HashMap<String, HashMap<String, TermFrequencies>>,
+    total_lengths: [usize; FIELD_COUNT],
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// 让索引与文章列表保持一致，返回索引是否发生变化
+    pub fn sync(&mut self, articles: &[Article]) -> bool {
+        let live = articles.iter().map(|article| article.info().aid.as_str()).collect::<HashSet<_>>();
+        let stale = self
+            .docs
+            .keys()
+            .filter(|aid| !live.contains(aid.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut changed = !stale.is_empty();
+        for aid in stale {
+            self.remove(&aid);
+        }
+        for article in articles {
+            let current = self.docs.get(&article.info().aid).is_some_and(|doc| is_current(doc.fingerprint, article));
+            if !current {
+                self.upsert(article);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// 文章是否已连同正文建立索引，且索引没有过期
+    pub fn has_content(&self, info: &ArticleInfo) -> bool {
+        self.docs.get(&info.aid).is_some_and(|doc| doc.fingerprint == (info.updated_at, true))
+    }
+
+    pub fn upsert(&mut self, article: &Article) {
+        let aid = article.info().aid.clone();
+        self.remove(&aid);
+
+        let text = plain_text(article.content());
+        let fields: [(Field, Vec<String>); FIELD_COUNT] = [
+            (Field::Title, tokenize(article.title())),
+            (Field::Summary, tokenize(&article.info().summary)),
+            (Field::Tags, article.tags().iter().flat_map(|tag| tokenize(tag)).collect()),
+            (Field::Categories, article.categories().iter().flat_map(|category| tokenize(category)).collect()),
+            (Field::Content, tokenize(&text)),
+        ];
+
+        let mut lengths = [0; FIELD_COUNT];
+        let mut frequencies: HashMap<String, TermFrequencies> = HashMap::new();
+        for (field, terms) in fields {
+            lengths[field.index()] = terms.len();
+            self.total_lengths[field.index()] += terms.len();
+            for term in terms {
+                frequencies.entry(term).or_default()[field.index()] += 1;
+            }
+        }
+
+        let terms = frequencies.keys().cloned().collect();
+        for (term, tf) in frequencies {
+            self.postings.entry(term).or_default().insert(aid.clone(), tf);
+        }
+        self.docs.insert(aid, IndexedDoc {
+            fingerprint: fingerprint(article),
+            lengths,
+            terms,
+            summary: article.info().summary.clone(),
+            text,
+        });
+    }
+
+    pub fn remove(&mut self, aid: &str) {
+        let Some(doc) = self.docs.remove(aid) else {
+            return;
+        };
+        for (total, len) in self.total_lengths.iter_mut().zip(doc.lengths) {
+            *total -= len;
+        }
+        for term in doc.terms {
+            if let Some(posting) = self.postings.get_mut(&term) {
+                posting.remove(aid);
+                if posting.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    /// 按相关度从高到低返回最多 `limit` 条结果。
+    /// 每个查询词会扩展为精确、前缀（仅 `prefix_last` 时的最后一个词）和模糊匹配的索引词，
+    /// 同一文章取各扩展中得分最高的一个，精确匹配的权重最高
+    pub fn search(&self, terms: &[String], prefix_last: bool, limit: usize) -> Vec<SearchHit> {
+        if self.docs.is_empty() {
+            return Vec::new();
+        }
+        let doc_count = self.docs.len() as f64;
+        let average_lengths = self.total_lengths.map(|total| (total as f64 / doc_count).max(1.0));
+
+        let mut scores: HashMap<&str, (f64, Vec<String>)> = HashMap::new();
+        let mut seen = HashSet::new();
+        for (i, term) in terms.iter().enumerate() {
+            if !seen.insert(term) {
+                continue;
+            }
+            let prefix = prefix_last && i + 1 == terms.len();
+            let mut best: HashMap<&str, (f64, &str)> = HashMap::new();
+            for (candidate, kind) in self.expand(term, prefix) {
+                let posting = &self.postings[candidate];
+                let df = posting.len() as f64;
+                let idf = (1.0 + (doc_count - df + 0.5) / (df + 0.5)).ln();
+                for (aid, tf) in posting {
+                    let Some(doc) = self.docs.get(aid) else {
+                        continue;
+                    };
+                    let weighted_tf = Field::ALL
+                        .iter()
+                        .map(|field| {
+                            let i = field.index();
+                            let norm = 1.0 - B + B * doc.lengths[i] as f64 / average_lengths[i];
+                            field.weight() * tf[i] as f64 / norm
+                        })
+                        .sum::<f64>();
+                    let score = kind.weight() * idf * weighted_tf / (K1 + weighted_tf);
+                    let entry = best.entry(aid.as_str()).or_insert((0.0, candidate));
+                    if score > entry.0 {
+                        *entry = (score, candidate);
+                    }
+                }
+            }
+            for (aid, (score, candidate)) in best {
+                let entry = scores.entry(aid).or_default();
+                entry.0 += score;
+                entry.1.push(candidate.to_string());
+            }
+        }
+
+        let mut hits = scores
+            .into_iter()
+            .map(|(aid, (score, terms))| SearchHit { aid: aid.to_string(), score, terms })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.aid.cmp(&b.aid)));
+        hits.truncate(limit);
+        hits
+    }
+
+    /// 查询词可以匹配到的索引词
+    fn expand<'a>(&'a self, term: &'a str, prefix: bool) -> Vec<(&'a str, MatchKind)> {
+        let mut expansions = Vec::new();
+        if self.postings.contains_key(term) {
+            expansions.push((term, MatchKind::Exact));
+        }
+        expansions.extend(
+            self.postings
+                .keys()
+                .filter(|candidate| candidate.as_str() != term)
+                .filter_map(|candidate| Some((candidate.as_str(), match_kind(term, candidate, prefix)?))),
+        );
+        expansions
+    }
+
+    /// 用于生成摘录的文本，按优先级排列：正文、摘要
+    pub fn excerpt_sources(&self, aid: &str) -> Vec<&str> {
+        self.docs
+            .get(aid)
+            .map(|doc| [doc.text.as_str(), doc.summary.as_str()].into_iter().filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn fingerprint(article: &Article) -> (DateTime<Utc>, bool) {
+    (article.info().updated_at, article.is_loaded())
+}
+
+/// 索引是否与文章一致。预先生成的索引带有正文，文章尚未加载正文时同样视为最新
+fn is_current((updated_at, has_content): (DateTime<Utc>, bool), article: &Article) -> bool {
+    updated_at == article.info().updated_at && (has_content || !article.is_loaded())
+}
+
+/// 提取 Markdown 中的纯文本
+fn plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(t) | Event::Code(t) | Event::InlineMath(t) | Event::DisplayMath(t) => {
+                text.push_str(&t);
+            }
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::blog::ArticleInfo, search::tokenize::query_terms};
+
+    fn article(aid: &str, title: &str, content: &str, updated: i64) -> Article {
+        Article::new(ArticleInfo::sample(aid).with_title(title).updated(updated), content, Vec::new())
+    }
+
+    fn aids(index: &SearchIndex, query: &str) -> Vec<String> {
+        index.search(&query_terms(query), false, 10).into_iter().map(|hit| hit.aid).collect()
+    }
+
+    #[test]
+    fn title_hits_rank_above_content_hits() {
+        let mut index = SearchIndex::new();
+        index.sync(&[
+            article("a", "Notes", "leptos signals and leptos memos", 0),
+            article("b", "Leptos", "a framework", 0),
+            article("c", "Other", "unrelated", 0),
+        ]);
+        assert_eq!(aids(&index, "leptos"), ["b", "a"]);
+    }
+
+    #[test]
+    fn sync_updates_incrementally() {
+        let mut index = SearchIndex::new();
+        let articles = vec![article("a", "Rust", "", 0), article("b", "Go", "", 0)];
+        assert!(index.sync(&articles));
+        assert!(!index.sync(&articles));
+
+        assert!(index.sync(&[article("a", "Zig", "", 1)]));
+        assert_eq!(index.len(), 1);
+        assert!(aids(&index, "rust").is_empty());
+        assert!(aids(&index, "go").is_empty());
+        assert_eq!(aids(&index, "zig"), ["a"]);
+    }
+
+    #[test]
+    fn serialized_index_round_trips() {
+        let mut index = SearchIndex::new();
+        index.sync(&[article("a", "Rust", "ownership and borrowing", 0), article("b", "Go", "goroutines", 0)]);
+        let json = serde_json::to_string(&index).unwrap();
+        let mut loaded: SearchIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(aids(&loaded, "borrowing"), ["a"]);
+        assert_eq!(loaded.excerpt_sources("b"), index.excerpt_sources("b"));
+
+        // 浏览器中文章只有摘要信息时，预先生成的正文索引仍然有效
+        let infos = [
+            Article::from_info(ArticleInfo::sample("a").with_title("Rust")),
+            Article::from_info(ArticleInfo::sample("b").with_title("Go").updated(1)),
+        ];
+        assert!(loaded.sync(&infos));
+        assert!(loaded.has_content(infos[0].info()));
+        assert!(!loaded.has_content(infos[1].info()));
+        assert_eq!(aids(&loaded, "borrowing"), ["a"]);
+        assert!(aids(&loaded, "goroutines").is_empty());
+    }
+
+    #[test]
+    fn indexes_markdown_as_plain_text() {
+        let mut index = SearchIndex::new();
+        index.sync(&[article("a", "T", "# Heading\n\n**bold** `code`", 0)]);
+        assert_eq!(index.excerpt_sources("a"), ["Heading bold code"]);
+        assert_eq!(aids(&index, "bold"), ["a"]);
+    }
+
+    #[test]
+    fn matches_chinese_queries() {
+        let mut index = SearchIndex::new();
+        index.sync(&[
+            article("a", "全文搜索引擎", "", 0),
+            article("b", "发动机", "汽车引擎的原理", 0),
+            article("c", "无关", "索引", 0),
+        ]);
+        assert_eq!(aids(&index, "搜索引擎")[0], "a");
+        assert_eq!(aids(&index, "ＳＥＡＲＣＨ 引擎"), ["a", "b"]);
+        assert!(aids(&index, "原").contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn tolerates_typos_and_ranks_exact_first() {
+        let mut index = SearchIndex::new();
+        index.sync(&[
+            article("a", "Leptos", "", 0),
+            article("b", "Leptso", "", 0),
+            article("c", "Pulldown", "", 0),
+        ]);
+        assert_eq!(aids(&index, "leptso"), ["b", "a"]);
+        assert_eq!(aids(&index, "pulldwon"), ["c"]);
+
+        let hits = index.search(&query_terms("pull"), true, 10);
+        assert_eq!(hits[0].aid, "c");
+        assert_eq!(hits[0].terms, ["pulldown"]);
+        assert!(index.search(&query_terms("pull"), false, 10).is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+pub mod fuzzy;
+pub mod index;
+pub mod query;
+pub mod recent;
+pub mod snippet;
+pub mod tokenize;
+
+use crate::models::blog::Article;
+use snippet::Segment;
+
+/// 一条搜索结果，标题和摘录已按命中的词切分好，便于高亮显示
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub article: Article,
+    pub score: f64,
+    pub title: Vec<Segment>,
+    pub snippet: Vec<Segment>,
+}
@@ -0,0 +1,369 @@
+use std::{collections::HashSet, error::Error, fmt};
+
+use chrono::NaiveDate;
+use chrono_tz::Asia::Shanghai;
+
+use crate::{
+    models::blog::Article,
+    search::tokenize::{normalize, query_terms},
+};
+
+/// 查询中的一个条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClauseKind {
+    /// 普通检索词
+    Term(String),
+    /// `"..."` 中的短语，要求原文连续出现
+    Phrase(String),
+    /// `tag:rust`
+    Tag(String),
+    /// `category:notes`
+    Category(String),
+    /// `before:2025-01-01`，发布日期早于该日
+    Before(NaiveDate),
+    /// `after:2025-01-01`，发布日期晚于该日
+    After(NaiveDate),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clause {
+    /// 以 `-` 开头的条件表示排除
+    pub negated: bool,
+    pub kind: ClauseKind,
+}
+
+/// 多个标签（分类）之间的组合方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FilterMode {
+    /// 满足其一即可
+    #[default]
+    Any,
+    /// 必须全部满足
+    All,
+}
+
+impl FilterMode {
+    /// 用于 URL 参数
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterMode::Any => "any",
+            FilterMode::All => "all",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "any" => Some(FilterMode::Any),
+            "all" => Some(FilterMode::All),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterMode::Any => "匹配任一",
+            FilterMode::All => "匹配全部",
+        }
+    }
+}
+
+/// 解析后的查询。
+///
+/// 求值规则：多个 `tag:` 之间、多个 `category:` 之间按 `mode` 组合（默认满足其一即可），
+/// 其余条件都必须满足，`-` 开头的条件必须不满足。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub clauses: Vec<Clause>,
+    pub mode: FilterMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnterminatedQuote,
+    EmptyValue(String),
+    InvalidDate { operator: String, value: String },
+    DanglingNegation,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::UnterminatedQuote => write!(f, "引号没有闭合"),
+            QueryError::EmptyValue(operator) => write!(f, "`{operator}:` 后面缺少内容"),
+            QueryError::InvalidDate { operator, value } => {
+                write!(f, "`{operator}:{value}` 不是有效日期，应为 YYYY-MM-DD")
+            }
+            QueryError::DanglingNegation => write!(f, "`-` 后面缺少要排除的内容"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let chars = input.chars().collect::<Vec<_>>();
+        let mut clauses = Vec::new();
+        let mut pos = 0;
+        while pos < chars.len() {
+            if chars[pos].is_whitespace() {
+                pos += 1;
+                continue;
+            }
+            let negated = chars[pos] == '-';
+            if negated {
+                pos += 1;
+                if chars.get(pos).is_none_or(|c| c.is_whitespace()) {
+                    return Err(QueryError::DanglingNegation);
+                }
+            }
+            let kind = if chars[pos] == '"' {
+                let (phrase, next) = read_quoted(&chars, pos)?;
+                pos = next;
+                ClauseKind::Phrase(phrase)
+            } else {
+                let start = pos;
+                while pos < chars.len() && !chars[pos].is_whitespace() && chars[pos] != ':' {
+                    pos += 1;
+                }
+                let word = chars[start..pos].iter().collect::<String>();
+                if chars.get(pos) == Some(&':') && is_operator(&word) {
+                    pos += 1;
+                    let value = if chars.get(pos) == Some(&'"') {
+                        let (value, next) = read_quoted(&chars, pos)?;
+                        pos = next;
+                        value
+                    } else {
+                        let start = pos;
+                        while pos < chars.len() && !chars[pos].is_whitespace() {
+                            pos += 1;
+                        }
+                        chars[start..pos].iter().collect()
+                    };
+                    operator_clause(&word, value.trim())?
+                } else {
+                    // 不认识的 `xxx:` 按普通文本处理
+                    while pos < chars.len() && !chars[pos].is_whitespace() {
+                        pos += 1;
+                    }
+                    ClauseKind::Term(chars[start..pos].iter().collect())
+                }
+            };
+            clauses.push(Clause { negated, kind });
+        }
+        Ok(Self { clauses, mode: FilterMode::Any })
+    }
+
+    /// 由筛选栏选中的标签和分类构造查询，与搜索框共用同一套求值规则
+    pub fn from_filters<'a>(
+        tags: impl IntoIterator<Item = &'a String>,
+        categories: impl IntoIterator<Item = &'a String>,
+        mode: FilterMode,
+    ) -> Self {
+        let tags = tags.into_iter().map(|tag| ClauseKind::Tag(tag.clone()));
+        let categories = categories.into_iter().map(|category| ClauseKind::Category(category.clone()));
+        Self {
+            clauses: tags.chain(categories).map(|kind| Clause { negated: false, kind }).collect(),
+            mode,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// 参与相关度排序的检索词：未被排除的普通词和短语
+    pub fn text_terms(&self) -> Vec<String> {
+        self.clauses
+            .iter()
+            .filter(|clause| !clause.negated)
+            .flat_map(|clause| match &clause.kind {
+                ClauseKind::Term(text) | ClauseKind::Phrase(text) => query_terms(text),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// 最后一个条件是否为可能尚未输入完的普通词
+    pub fn ends_with_term(&self) -> bool {
+        self.clauses
+            .last()
+            .is_some_and(|clause| !clause.negated && matches!(clause.kind, ClauseKind::Term(_)))
+    }
+
+    /// 文章是否满足除检索词相关度之外的全部条件
+    pub fn matches(&self, article: &Article) -> bool {
+        let mut tags = LabelMatch::default();
+        let mut categories = LabelMatch::default();
+        let mut text = None;
+        for clause in &self.clauses {
+            let satisfied = match &clause.kind {
+                // 普通词交给索引排序，这里不做过滤
+                ClauseKind::Term(_) if !clause.negated => continue,
+                ClauseKind::Term(value) | ClauseKind::Phrase(value) => {
+                    let text = text.get_or_insert_with(|| searchable_text(article));
+                    text.contains(&normalize(value))
+                }
+                ClauseKind::Tag(tag) => has_label(article.tags(), tag),
+                ClauseKind::Category(category) => has_label(article.categories(), category),
+                ClauseKind::Before(date) => published_on(article) < *date,
+                ClauseKind::After(date) => published_on(article) > *date,
+            };
+            match (&clause.kind, clause.negated) {
+                (ClauseKind::Tag(_), false) => tags.record(satisfied),
+                (ClauseKind::Category(_), false) => categories.record(satisfied),
+                (_, negated) if satisfied == negated => return false,
+                _ => {}
+            }
+        }
+        tags.passes(self.mode) && categories.passes(self.mode)
+    }
+}
+
+/// 一组标签（或分类）条件的命中情况
+#[derive(Default)]
+struct LabelMatch {
+    wanted: usize,
+    found: usize,
+}
+
+impl LabelMatch {
+    fn record(&mut self, satisfied: bool) {
+        self.wanted += 1;
+        self.found += usize::from(satisfied);
+    }
+
+    fn passes(&self, mode: FilterMode) -> bool {
+        match mode {
+            _ if self.wanted == 0 => true,
+            FilterMode::Any => self.found > 0,
+            FilterMode::All => self.found == self.wanted,
+        }
+    }
+}
+
+fn is_operator(word: &str) -> bool {
+    matches!(word.to_lowercase().as_str(), "tag" | "category" | "before" | "after")
+}
+
+fn operator_clause(operator: &str, value: &str) -> Result<ClauseKind, QueryError> {
+    let operator = operator.to_lowercase();
+    if value.is_empty() {
+        return Err(QueryError::EmptyValue(operator));
+    }
+    let date = || {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| QueryError::InvalidDate {
+            operator: operator.clone(),
+            value: value.to_string(),
+        })
+    };
+    Ok(match operator.as_str() {
+        "tag" => ClauseKind::Tag(value.to_string()),
+        "category" => ClauseKind::Category(value.to_string()),
+        "before" => ClauseKind::Before(date()?),
+        _ => ClauseKind::After(date()?),
+    })
+}
+
+/// 从 `start` 处的 `"` 读到下一个 `"`，返回内容和之后的位置
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), QueryError> {
+    let end = chars[start + 1..]
+        .iter()
+        .position(|c| *c == '"')
+        .map(|offset| start + 1 + offset)
+        .ok_or(QueryError::UnterminatedQuote)?;
+    Ok((chars[start + 1..end].iter().collect(), end + 1))
+}
+
+fn has_label(labels: &[String], wanted: &str) -> bool {
+    let wanted = normalize(wanted);
+    labels.iter().any(|label| normalize(label) == wanted)
+}
+
+fn published_on(article: &Article) -> NaiveDate {
+    article.info().created_at.with_timezone(&Shanghai).date_naive()
+}
+
+fn searchable_text(article: &Article) -> String {
+    let labels = article.tags().iter().chain(article.categories()).cloned().collect::<HashSet<_>>();
+    normalize(&format!(
+        "{}\n{}\n{}\n{}",
+        article.title(),
+        article.info().summary,
+        labels.into_iter().collect::<Vec<_>>().join("\n"),
+        article.content()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::models::blog::ArticleInfo;
+
+    fn clause(negated: bool, kind: ClauseKind) -> Clause {
+        Clause { negated, kind }
+    }
+
+    fn article(title: &str, tags: &[&str], created: &str) -> Article {
+        let created = DateTime::parse_from_rfc3339(created).unwrap().timestamp();
+        let info = ArticleInfo::sample(title).with_tags(tags).with_categories(&["notes"]).created(created);
+        Article::new(info, "some body text", Vec::new())
+    }
+
+    #[test]
+    fn parses_operators_phrases_and_negation() {
+        let query = Query::parse(r#"leptos tag:rust -category:"daily life" "body text" after:2025-01-01 -wasm"#).unwrap();
+        assert_eq!(query.clauses, [
+            clause(false, ClauseKind::Term("leptos".to_string())),
+            clause(false, ClauseKind::Tag("rust".to_string())),
+            clause(true, ClauseKind::Category("daily life".to_string())),
+            clause(false, ClauseKind::Phrase("body text".to_string())),
+            clause(false, ClauseKind::After(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())),
+            clause(true, ClauseKind::Term("wasm".to_string())),
+        ]);
+        assert_eq!(query.text_terms(), ["leptos", "body", "text"]);
+    }
+
+    #[test]
+    fn unknown_operators_are_plain_text() {
+        let query = Query::parse("http://example.com").unwrap();
+        assert_eq!(query.clauses, [clause(false, ClauseKind::Term("http://example.com".to_string()))]);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(Query::parse(r#""open"#), Err(QueryError::UnterminatedQuote));
+        assert_eq!(Query::parse("tag:"), Err(QueryError::EmptyValue("tag".to_string())));
+        assert!(matches!(Query::parse("before:2025-13-01"), Err(QueryError::InvalidDate { .. })));
+        assert_eq!(Query::parse("rust - "), Err(QueryError::DanglingNegation));
+    }
+
+    #[test]
+    fn evaluates_clauses() {
+        let rust = article("a", &["Rust"], "2025-03-01T00:00:00Z");
+        let go = article("b", &["Go"], "2024-03-01T00:00:00Z");
+        let matches = |input: &str, article: &Article| Query::parse(input).unwrap().matches(article);
+
+        assert!(matches("tag:rust", &rust));
+        assert!(!matches("tag:rust", &go));
+        assert!(matches("tag:rust tag:go", &go));
+        assert!(!matches("-tag:go", &go));
+        assert!(matches("after:2025-01-01", &rust));
+        assert!(!matches("before:2025-01-01", &rust));
+        assert!(matches(r#""body text""#, &rust));
+        assert!(!matches("-body", &rust));
+    }
+
+    #[test]
+    fn filter_modes() {
+        let both = article("a", &["rust", "wasm"], "2025-03-01T00:00:00Z");
+        let one = article("b", &["rust"], "2025-03-01T00:00:00Z");
+        let tags = ["rust".to_string(), "wasm".to_string()];
+        let any = Query::from_filters(&tags, &[], FilterMode::Any);
+        let all = Query::from_filters(&tags, &[], FilterMode::All);
+        assert!(any.matches(&both) && any.matches(&one));
+        assert!(all.matches(&both) && !all.matches(&one));
+    }
+}
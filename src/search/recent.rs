@@ -0,0 +1,28 @@
+use crate::utils::{storage_get, storage_set};
+
+const RECENT_KEY: &str = "rusite:recent-searches";
+/// 最多保留的最近搜索条数
+pub const MAX_RECENT: usize = 5;
+
+/// 读取最近的搜索，最新的在前
+pub fn load() -> Vec<String> {
+    storage_get(RECENT_KEY)
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// 记录一次搜索，重复的查询会被移到最前面，返回更新后的列表
+pub fn remember(query: &str) -> Vec<String> {
+    let query = query.trim();
+    let mut recent = load();
+    if query.is_empty() {
+        return recent;
+    }
+    recent.retain(|item| item != query);
+    recent.insert(0, query.to_string());
+    recent.truncate(MAX_RECENT);
+    if let Ok(raw) = serde_json::to_string(&recent) {
+        storage_set(RECENT_KEY, &raw);
+    }
+    recent
+}
@@ -0,0 +1,135 @@
+use crate::search::tokenize::normalize_char;
+
+/// 摘录中前后各保留的字符数
+pub const SNIPPET_RADIUS: usize = 40;
+
+/// 一段文本，`highlighted` 表示命中了检索词
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+impl Segment {
+    fn plain(text: String) -> Self {
+        Self { text, highlighted: false }
+    }
+}
+
+/// 在 `chars` 中查找所有检索词出现的位置，返回不重叠的 `[start, end)` 区间
+fn find_matches(chars: &[char], terms: &[String]) -> Vec<(usize, usize)> {
+    let terms = terms
+        .iter()
+        .map(|term| term.chars().collect::<Vec<_>>())
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>();
+    let normalized = chars.iter().copied().map(normalize_char).collect::<Vec<_>>();
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i < normalized.len() {
+        // 同一位置有多个检索词命中时取最长的
+        let longest = terms
+            .iter()
+            .filter(|term| normalized[i..].starts_with(term))
+            .map(Vec::len)
+            .max();
+        match longest {
+            Some(len) => {
+                matches.push((i, i + len));
+                i += len;
+            }
+            None => i += 1,
+        }
+    }
+    matches
+}
+
+fn segments(chars: &[char], matches: &[(usize, usize)], range: (usize, usize)) -> Vec<Segment> {
+    let (from, to) = range;
+    let mut result = Vec::new();
+    let mut cursor = from;
+    for &(start, end) in matches.iter().filter(|(start, end)| *start >= from && *end <= to) {
+        if start > cursor {
+            result.push(Segment::plain(chars[cursor..start].iter().collect()));
+        }
+        result.push(Segment { text: chars[start..end].iter().collect(), highlighted: true });
+        cursor = end;
+    }
+    if cursor < to {
+        result.push(Segment::plain(chars[cursor..to].iter().collect()));
+    }
+    result
+}
+
+/// 把整段文本按命中的检索词切分，用于标题高亮
+pub fn highlight(text: &str, terms: &[String]) -> Vec<Segment> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let matches = find_matches(&chars, terms);
+    segments(&chars, &matches, (0, chars.len()))
+}
+
+/// 从第一个包含检索词的文本中截取命中位置附近的摘录；
+/// 都不包含时返回第一段文本的开头
+pub fn excerpt(sources: &[&str], terms: &[String], radius: usize) -> Vec<Segment> {
+    for source in sources {
+        let chars = source.chars().collect::<Vec<_>>();
+        let matches = find_matches(&chars, terms);
+        let Some(&(first_start, first_end)) = matches.first() else {
+            continue;
+        };
+        let from = first_start.saturating_sub(radius);
+        let to = (first_end + radius).min(chars.len());
+        let mut result = segments(&chars, &matches, (from, to));
+        if from > 0 {
+            result.insert(0, Segment::plain("…".to_string()));
+        }
+        if to < chars.len() {
+            result.push(Segment::plain("…".to_string()));
+        }
+        return result;
+    }
+    sources
+        .first()
+        .map(|source| {
+            let chars = source.chars().collect::<Vec<_>>();
+            let mut result = segments(&chars, &[], (0, chars.len().min(radius * 2)));
+            if chars.len() > radius * 2 {
+                result.push(Segment::plain("…".to_string()));
+            }
+            result
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn highlights_terms_case_insensitively() {
+        let segments = highlight("Rust and rust", &terms(&["rust"]));
+        let marked = segments.iter().filter(|s| s.highlighted).map(|s| s.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(marked, ["Rust", "rust"]);
+    }
+
+    #[test]
+    fn excerpt_is_centered_on_first_match() {
+        let text = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let segments = excerpt(&[text.as_str()], &terms(&["needle"]), 10);
+        assert_eq!(segments.first().unwrap().text, "…");
+        assert_eq!(segments.last().unwrap().text, "…");
+        assert!(segments.iter().any(|s| s.highlighted && s.text == "needle"));
+        let len: usize = segments.iter().map(|s| s.text.chars().count()).sum();
+        assert_eq!(len, 10 + 6 + 10 + 2);
+    }
+
+    #[test]
+    fn excerpt_falls_back_to_later_sources() {
+        let segments = excerpt(&["nothing here", "summary with term"], &terms(&["term"]), 40);
+        assert!(segments.iter().any(|s| s.highlighted && s.text == "term"));
+    }
+}
@@ -0,0 +1,118 @@
+/// 统一字符形式：全角 ASCII 转半角、全角空格转半角空格、统一为小写。
+/// 每个字符只映射为一个字符，方便在原文中定位命中位置
+pub fn normalize_char(c: char) -> char {
+    let c = match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    };
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+pub fn normalize(text: &str) -> String {
+    text.chars().map(normalize_char).collect()
+}
+
+/// 中日韩文字没有词边界，按字切分
+pub fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // 平假名、片假名
+        | 0x3400..=0x4DBF   // 扩展 A
+        | 0x4E00..=0x9FFF   // 基本汉字
+        | 0xAC00..=0xD7AF   // 谚文
+        | 0xF900..=0xFAFF   // 兼容汉字
+        | 0x20000..=0x2FFFF // 扩展 B 及以后
+    )
+}
+
+/// 连续的同类字符
+enum Run {
+    Word(String),
+    Cjk(Vec<char>),
+}
+
+fn runs(text: &str) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut word = String::new();
+    let mut cjk = Vec::new();
+    for c in text.chars().map(normalize_char) {
+        if is_cjk(c) {
+            if !word.is_empty() {
+                runs.push(Run::Word(std::mem::take(&mut word)));
+            }
+            cjk.push(c);
+        } else {
+            if !cjk.is_empty() {
+                runs.push(Run::Cjk(std::mem::take(&mut cjk)));
+            }
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else if !word.is_empty() {
+                runs.push(Run::Word(std::mem::take(&mut word)));
+            }
+        }
+    }
+    if !word.is_empty() {
+        runs.push(Run::Word(word));
+    }
+    if !cjk.is_empty() {
+        runs.push(Run::Cjk(cjk));
+    }
+    runs
+}
+
+fn bigrams(chars: &[char]) -> impl Iterator<Item = String> + '_ {
+    chars.windows(2).map(|pair| pair.iter().collect())
+}
+
+/// 将文本切分为索引词：拉丁字母和数字按词切分，中日韩文字同时产生单字和相邻两字组合，
+/// 这样单字查询和多字查询都能命中
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for run in runs(text) {
+        match run {
+            Run::Word(word) => terms.push(word),
+            Run::Cjk(chars) => {
+                terms.extend(chars.iter().map(char::to_string));
+                terms.extend(bigrams(&chars));
+            }
+        }
+    }
+    terms
+}
+
+/// 将查询切分为检索词：中日韩文字只用相邻两字组合（单字时用单字），
+/// 避免“搜索引擎”被拆成单字后命中只含“引”的文章
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for run in runs(query) {
+        match run {
+            Run::Word(word) => terms.push(word),
+            Run::Cjk(chars) if chars.len() == 1 => terms.push(chars[0].to_string()),
+            Run::Cjk(chars) => terms.extend(bigrams(&chars)),
+        }
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_full_width_and_case() {
+        assert_eq!(normalize("ＲＵＳＴ　１２３"), "rust 123");
+        assert_eq!(tokenize("Ｌｅｐｔｏｓ，Rust"), ["leptos", "rust"]);
+    }
+
+    #[test]
+    fn splits_mixed_chinese_and_english() {
+        assert_eq!(tokenize("用Rust写博客"), ["用", "rust", "写", "博", "客", "写博", "博客"]);
+    }
+
+    #[test]
+    fn queries_use_bigrams_for_chinese() {
+        assert_eq!(query_terms("搜索引擎 wasm"), ["搜索", "索引", "引擎", "wasm"]);
+        assert_eq!(query_terms("博"), ["博"]);
+    }
+}
@@ -0,0 +1,119 @@
+use serde_json::{json, Value};
+
+use crate::{feeds::escape_xml, models::{blog::ArticleInfo, markdown::FrontMatter}};
+
+pub const SITE_NAME: &str = "Rusite";
+pub const SITE_DESCRIPTION: &str = "Rusite 博客";
+
+/// 全站标题模板：`页面标题 | Rusite`，没有页面标题时只显示站点名
+pub fn title_template(text: String) -> String {
+    let title = if text.is_empty() || text == SITE_NAME {
+        SITE_NAME.to_string()
+    } else {
+        format!("{text} | {SITE_NAME}")
+    };
+    // leptos_meta 在服务端输出 `<title>` 时不做转义
+    if cfg!(feature = "ssr") { escape_xml(&title) } else { title }
+}
+
+/// 站点根地址，不带末尾的 `/`。浏览器中取当前页面的 origin，其余环境读取 `SITE_URL`
+#[cfg(target_arch = "wasm32")]
+pub fn site_url() -> String {
+    web_sys::window()
+        .and_then(|window| window.location().origin().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn site_url() -> String {
+    std::env::var("SITE_URL")
+        .unwrap_or_else(|_| "http://127.0.0.1:8080".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn absolute_url(path: &str) -> String {
+    format!("{}{}", site_url(), path)
+}
+
+pub fn article_path(aid: &str) -> String {
+    format!("/blog/{aid}")
+}
+
+/// 文章的 schema.org `BlogPosting` 结构化数据，Front Matter 中的标题和标签优先
+pub fn blog_posting(info: &ArticleInfo, front_matter: Option<&FrontMatter>, url: &str) -> Value {
+    let headline = front_matter
+        .map(|front_matter| front_matter.title.as_str())
+        .filter(|title| !title.is_empty())
+        .unwrap_or(&info.title);
+    let mut keywords = front_matter.map(|front_matter| front_matter.tags.clone()).unwrap_or_default();
+    for keyword in info.tags.iter().chain(&info.categories) {
+        if !keywords.contains(keyword) {
+            keywords.push(keyword.clone());
+        }
+    }
+    json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": headline,
+        "description": info.summary,
+        "url": url,
+        "mainEntityOfPage": { "@type": "WebPage", "@id": url },
+        "datePublished": info.created_at.to_rfc3339(),
+        "dateModified": info.updated_at.to_rfc3339(),
+        "keywords": keywords,
+        "publisher": { "@type": "Organization", "name": SITE_NAME },
+    })
+}
+
+/// 序列化为可以直接放进 `<script type="application/ld+json">` 的文本，
+/// 转义 `<` 以免内容中的 `</script>` 提前结束脚本
+pub fn json_ld(value: &Value) -> String {
+    value.to_string().replace('<', "\\u003c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> ArticleInfo {
+        ArticleInfo::sample("a")
+            .with_title("From API")
+            .with_summary("</script><b>")
+            .with_tags(&["rust"])
+            .with_categories(&["notes"])
+            .updated(60)
+    }
+
+    #[test]
+    fn formats_titles() {
+        assert_eq!(title_template("Blog".to_string()), "Blog | Rusite");
+        assert_eq!(title_template(SITE_NAME.to_string()), SITE_NAME);
+        assert_eq!(title_template(String::new()), SITE_NAME);
+    }
+
+    #[test]
+    fn front_matter_overrides_article_info() {
+        let front_matter = FrontMatter {
+            title: "From Front Matter".to_string(),
+            date: "2025-01-01".to_string(),
+            tags: vec!["wasm".to_string(), "rust".to_string()],
+            categories: Vec::new(),
+        };
+        let posting = blog_posting(&info(), Some(&front_matter), "https://example.com/blog/a");
+        assert_eq!(posting["headline"], "From Front Matter");
+        assert_eq!(posting["keywords"], json!(["wasm", "rust", "notes"]));
+        assert_eq!(posting["dateModified"], "1970-01-01T00:01:00+00:00");
+
+        let posting = blog_posting(&info(), None, "https://example.com/blog/a");
+        assert_eq!(posting["headline"], "From API");
+    }
+
+    #[test]
+    fn json_ld_cannot_close_the_script_tag() {
+        let text = json_ld(&blog_posting(&info(), None, "https://example.com/blog/a"));
+        assert!(!text.contains("</script>"));
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed["description"], "</script><b>");
+    }
+}
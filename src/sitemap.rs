@@ -0,0 +1,109 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::{
+    app::{PRIVATE_ROUTES, STATIC_ROUTES},
+    feeds::escape_xml,
+    models::blog::ArticleInfo,
+};
+
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+pub const ROBOTS_FILE: &str = "robots.txt";
+
+/// 站点地图中的一个地址
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+/// 收集 `app.rs` 中的公开路由和所有文章页。
+/// 列表类页面的修改时间取最近更新的文章，设置了访问密码的文章不收录
+pub fn collect_urls(site_url: &str, articles: &[ArticleInfo]) -> Vec<SitemapUrl> {
+    let site_url = site_url.trim_end_matches('/');
+    let public = articles.iter().filter(|info| info.secret.is_none()).collect::<Vec<_>>();
+    let latest = public.iter().map(|info| info.updated_at).max();
+
+    let mut urls = STATIC_ROUTES
+        .iter()
+        .map(|route| SitemapUrl { loc: format!("{site_url}{route}"), lastmod: latest })
+        .collect::<Vec<_>>();
+    urls.extend(public.iter().map(|info| SitemapUrl {
+        loc: format!("{site_url}/blog/{}", info.aid),
+        lastmod: Some(info.updated_at),
+    }));
+    urls
+}
+
+pub fn render_sitemap(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    xml.push('\n');
+    for url in urls {
+        let _ = write!(xml, "<url><loc>{}</loc>", escape_xml(&url.loc));
+        if let Some(lastmod) = url.lastmod {
+            let _ = write!(xml, "<lastmod>{}</lastmod>", lastmod.to_rfc3339_opts(SecondsFormat::Secs, true));
+        }
+        xml.push_str("</url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// 允许抓取公开页面，屏蔽后台和账号相关的页面
+pub fn render_robots(site_url: &str) -> String {
+    let mut robots = String::from("User-agent: *\nAllow: /\n");
+    for route in PRIVATE_ROUTES {
+        let _ = writeln!(robots, "Disallow: {route}");
+    }
+    let _ = writeln!(robots, "\nSitemap: {}/{SITEMAP_FILE}", site_url.trim_end_matches('/'));
+    robots
+}
+
+#[cfg(test)]
+mod tests {
+    use quick_xml::{events::Event, Reader};
+
+    use super::*;
+
+    #[test]
+    fn lists_static_routes_and_public_articles() {
+        let articles = [ArticleInfo::sample("a").updated(60), ArticleInfo::sample("b").updated(120).with_secret("pw")];
+        let urls = collect_urls("https://example.com/", &articles);
+        let locs = urls.iter().map(|url| url.loc.as_str()).collect::<Vec<_>>();
+        assert_eq!(locs.len(), STATIC_ROUTES.len() + 1);
+        assert!(locs.contains(&"https://example.com/"));
+        assert!(locs.contains(&"https://example.com/blog/a"));
+        assert!(!locs.contains(&"https://example.com/blog/b"));
+        assert!(urls.iter().all(|url| url.lastmod == DateTime::from_timestamp(60, 0)));
+    }
+
+    #[test]
+    fn renders_well_formed_sitemap() {
+        let xml = render_sitemap(&collect_urls("https://example.com", &[ArticleInfo::sample("a&b").updated(60)]));
+        let mut reader = Reader::from_str(&xml);
+        let mut locs = Vec::new();
+        let mut in_loc = false;
+        loop {
+            match reader.read_event().expect("sitemap should be well-formed XML") {
+                Event::Start(tag) => in_loc = tag.name().as_ref() == b"loc",
+                Event::Text(text) if in_loc => locs.push(text.unescape().unwrap().into_owned()),
+                Event::End(_) => in_loc = false,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert_eq!(locs.last().unwrap(), "https://example.com/blog/a&b");
+        assert!(xml.contains("<lastmod>1970-01-01T00:01:00Z</lastmod>"));
+    }
+
+    #[test]
+    fn robots_points_to_sitemap() {
+        let robots = render_robots("https://example.com/");
+        assert!(robots.contains("Disallow: /admin\n"));
+        assert!(robots.ends_with("Sitemap: https://example.com/sitemap.xml\n"));
+    }
+}
@@ -0,0 +1,59 @@
+//! 服务端渲染，供 `rusite-server` 和 `rusite-export` 共用
+
+use std::sync::Arc;
+
+use futures::{stream, StreamExt};
+use hydration_context::{SharedContext, SsrSharedContext};
+use leptos::prelude::*;
+use leptos_meta::ServerMetaContext;
+use leptos_router::location::RequestUrl;
+
+use crate::{app::App, state::Preloaded};
+
+/// 渲染 `url` 对应的页面并填入 trunk 生成的 `index.html` 模板：
+/// 应用渲染在 `<body>` 开头，预加载的数据和页面元数据放在 `<head>` 中。
+/// 调用前需要先初始化异步执行器（`any_spawner::Executor::init_tokio`）。
+/// 预加载的数据无法序列化时返回错误。
+pub async fn render_page(template: &str, url: &str, preloaded: Option<Preloaded>) -> Result<String, serde_json::Error> {
+    let (meta_context, meta_output) = ServerMetaContext::new();
+    let shared_context: Arc<dyn SharedContext + Send + Sync> = Arc::new(SsrSharedContext::new());
+    let owner = Owner::new_root(Some(shared_context.clone()));
+    let app_html = owner
+        .with(|| {
+            provide_context(RequestUrl::new(url));
+            provide_context(meta_context);
+            if let Some(preloaded) = preloaded.clone() {
+                provide_context(preloaded);
+            }
+            view! { <App /> }.to_html_stream_in_order()
+        })
+        .collect::<String>()
+        .await;
+    // 水合所需的 `__RESOLVED_RESOURCES` 等全局变量
+    let mut resources = String::new();
+    if let Some(mut pending) = shared_context.pending_data() {
+        while let Some(chunk) = pending.next().await {
+            resources.push_str(&format!("<script>{chunk}</script>"));
+        }
+    }
+
+    let preloaded = preloaded.map(|preloaded| preloaded.to_script()).transpose()?.unwrap_or_default();
+    let page = strip_title(template)
+        .replacen("</head>", &format!("{preloaded}</head>"), 1)
+        .replacen("<body>", &format!("<body>{app_html}{resources}"), 1);
+    Ok(meta_output
+        .inject_meta_context(stream::once(async move { page }).boxed())
+        .await
+        .collect::<String>()
+        .await)
+}
+
+/// 去掉模板中的 `<title>`，标题由 leptos_meta 按页面生成
+fn strip_title(template: &str) -> String {
+    match (template.find("<title>"), template.find("</title>")) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}", &template[..start], &template[end + "</title>".len()..])
+        }
+        _ => template.to_string(),
+    }
+}
@@ -1,85 +1,328 @@
-use crate::{api::blog::{get_all_articles_details, get_categories, get_tags}, models::blog::{Article, Category, Tag}};
-use leptos::prelude::*;
-use std::collections::{HashMap, HashSet};
+use crate::{api::{auth, blog::{cached_article_details, cached_blog_data, get_article_details, get_articles_list, get_categories, get_search_index, get_tags, prefetch_articles_details}, error::ApiError, session}, models::{archive::YearMonth, blog::{Article, ArticleInfo, Category, Comment, SortOrder, Tag}, user::{LoginRequest, User, UserCreateRequest}}, search::{index::{SearchHit, SearchIndex}, query::{FilterMode, Query, QueryError}, snippet::{excerpt, highlight, SNIPPET_RADIUS}, SearchResult}, utils::{load_timezone, save_timezone, DEFAULT_TIMEZONE}};
+use chrono_tz::Tz;
+use futures::{try_join, StreamExt};
+use leptos::{prelude::*, task::spawn_local};
+use serde::{Deserialize, Serialize};
+use std::{collections::{HashMap, HashSet}, pin::pin};
+
+/// 后台预取文章详情时的最大并发请求数
+const PREFETCH_CONCURRENCY: usize = 4;
+/// 搜索面板最多显示的结果数
+const SEARCH_LIMIT: usize = 10;
 
 #[derive(Clone, Debug)]
 pub struct AppState { 
     // common
     pub loading: RwSignal<bool>,
+    pub error: RwSignal<Option<ApiError>>,
     pub active: RwSignal<bool>,
     pub current_page: RwSignal<usize>,
     pub items_per_page: RwSignal<usize>,
+    /// 读者所在时区，用于按年月归档
+    pub timezone: RwSignal<Tz>,
     pub articles: RwSignal<Vec<Article>>,
     pub aid_map: RwSignal<HashMap<String, usize>>,
+    /// 正在加载详情的文章，避免重复请求
+    pub details_pending: RwSignal<HashSet<String>>,
     // filter bar state
     pub tags: RwSignal<Vec<Tag>>,
     pub categories: RwSignal<Vec<Category>>,
     pub selected_tags: RwSignal<HashSet<String>>,
     pub selected_categories: RwSignal<HashSet<String>>,
+    /// 多个标签、分类之间是满足其一还是全部满足
+    pub filter_mode: RwSignal<FilterMode>,
+    pub sort_order: RwSignal<SortOrder>,
+    /// 只看某个月发布的文章，从归档页进入时设置
+    pub selected_month: RwSignal<Option<YearMonth>>,
     pub filtered_results: Memo<Vec<Article>>,
     // search bar state
     pub search_query: RwSignal<String>,
-    pub search_results: Memo<Vec<Article>>,
+    /// 搜索框内容解析出的查询，解析失败时在搜索面板中提示
+    pub parsed_query: Memo<Result<Query, QueryError>>,
+    /// 随 `articles` 增量更新的全文索引
+    pub search_index: RwSignal<SearchIndex>,
+    pub prebuilt_index: RwSignal<PrebuiltIndex>,
+    pub search_results: Memo<Vec<SearchResult>>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AppState {
     pub fn new() -> Self {
         let articles = RwSignal::new(Vec::new());
+        let aid_map = RwSignal::new(HashMap::new());
         let selected_tags = RwSignal::new(HashSet::new());
         let selected_categories = RwSignal::new(HashSet::new());
         let search_query = RwSignal::new(String::new());
-        let filtered_results = create_filtered_results(articles, selected_tags, selected_categories);
-        let search_results = create_search_results(articles, search_query);
+        let filter_mode = RwSignal::new(FilterMode::default());
+        let sort_order = RwSignal::new(SortOrder::default());
+        // 与 `AuthState` 相同，服务端渲染和水合时读者设置的时区要等挂载后才读取，
+        // 以保证首次渲染与服务端一致
+        let timezone = RwSignal::new(if cfg!(feature = "csr") { load_timezone() } else { DEFAULT_TIMEZONE });
+        if !cfg!(feature = "csr") {
+            Effect::new(move |_| {
+                let stored = load_timezone();
+                if stored != timezone.get_untracked() {
+                    timezone.set(stored);
+                }
+            });
+        }
+        let selected_month = RwSignal::new(None);
+        let filtered_results = create_filtered_results(
+            articles, selected_tags, selected_categories, filter_mode, sort_order, timezone, selected_month,
+        );
+        let search_index = RwSignal::new(SearchIndex::new());
+        Effect::new(move |_| {
+            articles.with(|articles| search_index.maybe_update(|index| index.sync(articles)));
+        });
+        let parsed_query = Memo::new(move |_| search_query.with(|query| Query::parse(query)));
+        let search_results = create_search_results(articles, aid_map, search_index, search_query, parsed_query);
         Self {
             loading: RwSignal::new(true),
+            error: RwSignal::new(None),
             active: RwSignal::new(false),
             current_page: RwSignal::new(1),
             items_per_page: RwSignal::new(10),
+            timezone,
             articles,
-            aid_map: RwSignal::new(HashMap::new()),
-            //
-
-
-
-            
+            aid_map,
+            details_pending: RwSignal::new(HashSet::new()),
+            // filter bar state
             tags: RwSignal::new(Vec::new()),
             categories: RwSignal::new(Vec::new()),
             selected_tags,
             selected_categories,
+            filter_mode,
+            sort_order,
+            selected_month,
             filtered_results,
-            // 
+            // search bar state
             search_query,
+            parsed_query,
+            search_index,
+            prebuilt_index: RwSignal::new(PrebuiltIndex::default()),
             search_results,
         }
     }
+    /// 加载文章列表、标签和分类。
+    /// 有本地缓存时先用缓存渲染（stale-while-revalidate），再用后端的最新数据替换。
     pub async fn load_data(&self) { 
-        let articles = get_all_articles_details().await;
-        self.aid_map.update(|map| map.clear());
-        for idx in 0..articles.len() {
-            self.aid_map.update(|map| {
-                map.insert(articles[idx].aid().clone(), idx);
-            });
+        self.error.set(None);
+        let has_cache = match cached_blog_data() {
+            Some((infos, tags, categories)) => {
+                self.apply_data(infos, tags, categories);
+                self.loading.set(false);
+                true
+            }
+            // 服务端渲染时已经预加载了数据，后台刷新即可
+            None if self.articles.with_untracked(|articles| !articles.is_empty()) => true,
+            None => {
+                self.loading.set(true);
+                false
+            }
+        };
+        match fetch_all().await {
+            Ok((infos, tags, categories)) => self.apply_data(infos, tags, categories),
+            Err(err) if has_cache => {
+                log::warn!("failed to revalidate blog data, showing cached copy: {err}");
+            }
+            Err(err) => {
+                log::error!("failed to load blog data: {err}");
+                self.error.set(Some(err));
+            }
         }
-        let tags = get_tags().await;
-        let categories = get_categories().await;
-        self.articles.set(articles.clone());
+        self.loading.set(false);
+    }
+    /// 用新的文章列表替换当前列表。`updated_at` 未变化的文章保留已加载的详情，
+    /// 其余文章尝试从缓存还原，否则以“仅摘要”状态等待按需加载。
+    fn apply_data(&self, infos: Vec<ArticleInfo>, tags: Vec<Tag>, categories: Vec<Category>) {
+        let articles = self.articles.with_untracked(|current| {
+            let current = current
+                .iter()
+                .map(|article| (article.info().aid.as_str(), article))
+                .collect::<HashMap<_, _>>();
+            infos
+                .into_iter()
+                .map(|info| match current.get(info.aid.as_str()) {
+                    Some(existing) if existing.is_loaded() && existing.info().updated_at == info.updated_at => {
+                        existing.with_info(info)
+                    }
+                    _ => cached_article_details(&info).unwrap_or_else(|| Article::from_info(info)),
+                })
+                .collect::<Vec<_>>()
+        });
+        self.aid_map.set(
+            articles
+                .iter()
+                .enumerate()
+                .map(|(idx, article)| (article.aid(), idx))
+                .collect()
+        );
+        self.articles.set(articles);
         self.tags.set(tags);
         self.categories.set(categories);
-        self.loading.update(|v| *v = false);
-    }
-    pub fn  
-       
-       
-       
-       
-       
-       
-       
-       
-       
-       get_article(&self, aid: String) -> Memo<Option<Article>> {
-        let aid_map = self.aid_map.clone();
-        let articles = self.articles.clone();
+    }
+    /// 重新加载数据，用于错误页面的“重试”按钮
+    pub fn reload(&self) {
+        let state = self.clone();
+        spawn_local(async move {
+            state.load_data().await;
+        });
+    }
+    /// 按需加载单篇文章的正文和评论，并将其从“仅摘要”状态升级。
+    /// 详情从缓存还原的文章同样会重新请求，失败时继续显示缓存的副本。
+    /// 已有请求（例如后台预取）在进行时直接返回，调用方应在 `is_details_pending` 变为
+    /// `false` 后检查文章是否已加载，未加载则重新调用
+    pub async fn load_article_details(&self, aid: &str) -> Result<(), ApiError> {
+        let Some(info) = self.article_info_needing_details(aid) else {
+            return Ok(());
+        };
+        if !self.mark_pending(aid) {
+            return Ok(());
+        }
+        let result = get_article_details(info).await;
+        self.details_pending.update(|pending| {
+            pending.remove(aid);
+        });
+        match result {
+            Ok(article) => self.set_article(article),
+            Err(err) if self.get_article_untracked(aid).is_some_and(|article| article.is_loaded()) => {
+                log::warn!("failed to revalidate article {aid}, showing cached copy: {err}");
+                self.update_article(aid, Article::revalidated);
+            }
+            Err(err) => return Err(err),
+        }
+        Ok(())
+    }
+    /// 在后台以有限并发预取所有尚未加载的文章详情（供全文搜索使用）。
+    /// 站点提供了预先生成的搜索索引时先下载索引，只预取索引没有覆盖的文章
+    pub fn prefetch_details(&self) {
+        match self.prebuilt_index.get_untracked() {
+            PrebuiltIndex::Loading => return,
+            PrebuiltIndex::Available => {
+                self.prebuilt_index.set(PrebuiltIndex::Loading);
+                let state = self.clone();
+                spawn_local(async move {
+                    match get_search_index().await {
+                        Ok(mut index) => {
+                            state.articles.with_untracked(|articles| index.sync(articles));
+                            state.search_index.set(index);
+                        }
+                        Err(err) => log::warn!("failed to load search index: {err}"),
+                    }
+                    state.prebuilt_index.set(PrebuiltIndex::Unavailable);
+                    state.prefetch_details();
+                });
+                return;
+            }
+            PrebuiltIndex::Unavailable => {}
+        }
+        let infos = self.articles.with_untracked(|articles| {
+            self.search_index.with_untracked(|index| {
+                articles
+                    .iter()
+                    .filter(|article| !article.is_loaded() && !index.has_content(article.info()))
+                    .map(|article| article.info().clone())
+                    .collect::<Vec<_>>()
+            })
+        });
+        let infos = infos
+            .into_iter()
+            .filter(|info| self.mark_pending(&info.aid))
+            .collect::<Vec<_>>();
+        if infos.is_empty() {
+            return;
+        }
+        let state = self.clone();
+        spawn_local(async move {
+            let mut results = pin!(prefetch_articles_details(infos, PREFETCH_CONCURRENCY));
+            while let Some((aid, result)) = results.next().await {
+                state.details_pending.update(|pending| {
+                    pending.remove(&aid);
+                });
+                match result {
+                    Ok(article) => state.set_article(article),
+                    Err(err) => log::warn!("failed to prefetch article {aid}: {err}"),
+                }
+            }
+        });
+    }
+    /// 修改某篇文章的评论列表，用于评论的乐观更新
+    pub fn update_comments(&self, aid: &str, f: impl FnOnce(&mut Vec<Comment>)) {
+        self.update_article(aid, |article| {
+            let mut comments = article.comments().to_vec();
+            f(&mut comments);
+            article.with_comments(comments)
+        });
+    }
+    fn update_article(&self, aid: &str, f: impl FnOnce(&Article) -> Article) {
+        let Some(idx) = self.aid_map.with_untracked(|map| map.get(aid).copied()) else {
+            return;
+        };
+        self.articles.update(|articles| {
+            if let Some(article) = articles.get_mut(idx) {
+                *article = f(article);
+            }
+        });
+    }
+    fn get_article_untracked(&self, aid: &str) -> Option<Article> {
+        let idx = self.aid_map.with_untracked(|map| map.get(aid).copied())?;
+        self.articles.with_untracked(|articles| articles.get(idx).cloned())
+    }
+    /// 文章详情是否正在加载（响应式）
+    pub fn is_details_pending(&self, aid: &str) -> bool {
+        self.details_pending.with(|pending| pending.contains(aid))
+    }
+    fn article_info_needing_details(&self, aid: &str) -> Option<ArticleInfo> {
+        self.get_article_untracked(aid)
+            .filter(Article::needs_details)
+            .map(|article| article.info().clone())
+    }
+    /// 标记文章详情正在加载，已在加载中时返回 `false`
+    fn mark_pending(&self, aid: &str) -> bool {
+        let mut inserted = false;
+        self.details_pending.update(|pending| {
+            inserted = pending.insert(aid.to_string());
+        });
+        inserted
+    }
+    /// 应用服务端预加载的数据，渲染时无需再等待请求
+    pub fn apply_preloaded(&self, preloaded: Preloaded) {
+        let Preloaded { articles: infos, tags, categories, details, search_index } = preloaded;
+        if search_index {
+            self.prebuilt_index.set(PrebuiltIndex::Available);
+        }
+        let articles = details
+            .into_iter()
+            .filter_map(|detail| {
+                let info = infos.iter().find(|info| info.aid == detail.aid)?;
+                Some(Article::new(info.clone(), &detail.content, detail.comments))
+            })
+            .collect::<Vec<_>>();
+        self.apply_data(infos, tags, categories);
+        for article in articles {
+            self.set_article(article);
+        }
+        self.loading.set(false);
+    }
+    /// 用新的文章数据替换列表中的同名文章
+    fn set_article(&self, article: Article) {
+        let Some(idx) = self.aid_map.with_untracked(|map| map.get(&article.aid()).copied()) else {
+            return;
+        };
+        self.articles.update(|articles| {
+            if let Some(slot) = articles.get_mut(idx) {
+                *slot = article;
+            }
+        });
+    }
+    pub fn get_article(&self, aid: String) -> Memo<Option<Article>> {
+        let aid_map = self.aid_map;
+        let articles = self.articles;
         Memo::new(move |_| {
             aid_map.with(|map| map.get(&aid).copied())
                 .and_then(|idx| articles.with(|articles| articles.get(idx).cloned()))
@@ -113,54 +356,98 @@ impl AppState {
     }
     pub fn clear_filters(&self) { 
         clear_filters(self.selected_tags, self.selected_categories);
+        self.selected_month.set(None);
+    }
+    /// 切换时区并记住读者的选择
+    pub fn set_timezone(&self, timezone: Tz) {
+        self.timezone.set(timezone);
+        save_timezone(timezone);
+    }
+    /// 当前筛选条件下的总页数
+    pub fn total_pages(&self) -> usize {
+        self.filtered_results.with(Vec::len).div_ceil(self.items_per_page.get()).max(1)
     }
 
 }
 
+/// 首屏只需要文章列表、标签和分类，正文与评论留到打开文章时再加载
+async fn fetch_all() -> Result<(Vec<ArticleInfo>, Vec<Tag>, Vec<Category>), ApiError> {
+    try_join!(get_articles_list(), get_tags(), get_categories())
+}
+
 fn create_filtered_results(
     articles: RwSignal<Vec<Article>>, 
     selected_tags: RwSignal<HashSet<String>>,
     selected_categories: RwSignal<HashSet<String>>,
+    filter_mode: RwSignal<FilterMode>,
+    sort_order: RwSignal<SortOrder>,
+    timezone: RwSignal<Tz>,
+    selected_month: RwSignal<Option<YearMonth>>,
 ) -> Memo<Vec<Article>> {
     Memo::new( move |_| {
-        articles.with(|articles| {
+        let query = selected_tags.with(|tags| {
+            selected_categories.with(|categories| Query::from_filters(tags, categories, filter_mode.get()))
+        });
+        let order = sort_order.get();
+        let month = selected_month.get();
+        let timezone = timezone.get();
+        let mut results = articles.with(|articles| {
             articles
                 .iter()
-                .filter(|article| {
-                let tags_empty = selected_tags.with(|t| t.is_empty());
-                tags_empty || article.tags().iter().any(|t| selected_tags.with(|st| st.contains(t)))
-            })
-            .filter(|article| {
-                let categories_empty = selected_categories.with(|c| c.is_empty());
-                categories_empty || article.categories().iter().any(|c| selected_categories.with(|sc| sc.contains(c)))
-            })
-            .cloned()
-            .collect()
-        })}
-    )
+                .filter(|article| query.matches(article))
+                .filter(|article| month.is_none_or(|month| YearMonth::of(article.info().created_at, timezone) == month))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        results.sort_by(|a, b| order.compare(a, b));
+        results
+    })
 }
 
 fn create_search_results(
     articles: RwSignal<Vec<Article>>,
-    search_query: RwSignal<String>, 
-) -> Memo<Vec<Article>> {
-    Memo::new( move |_| {
-        let query = search_query.get().trim().to_lowercase();
-        
-        if query.is_empty() {
-            return Vec::new();
-        }
-        articles.with(|articles| {
-            articles
-                .iter()
-                .filter(|article| {
-                    article.title().to_lowercase().contains(&query) ||
-                    article.content().to_lowercase().contains(&query) ||
-                    article.tags().iter().any(|tag| tag.to_lowercase().contains(&query)) ||
-                    article.categories().iter().any(|category| category.to_lowercase().contains(&query))
+    aid_map: RwSignal<HashMap<String, usize>>,
+    search_index: RwSignal<SearchIndex>,
+    search_query: RwSignal<String>,
+    parsed_query: Memo<Result<Query, QueryError>>,
+) -> Memo<Vec<SearchResult>> {
+    Memo::new(move |_| {
+        let query = match parsed_query.get() {
+            Ok(query) if !query.is_empty() => query,
+            _ => return Vec::new(),
+        };
+        let terms = query.text_terms();
+        // 末尾没有空白时，最后一个词可能还没输入完
+        let prefix_last = query.ends_with_term()
+            && search_query.with(|query| !query.ends_with(char::is_whitespace));
+        search_index.with(|index| {
+            let hits = if terms.is_empty() {
+                // 只有筛选条件时按发布时间倒序列出
+                articles.with(|articles| {
+                    let mut articles = articles.iter().collect::<Vec<_>>();
+                    articles.sort_by_key(|article| std::cmp::Reverse(article.info().created_at));
+                    articles
+                        .into_iter()
+                        .map(|article| SearchHit { aid: article.aid(), score: 0.0, terms: Vec::new() })
+                        .collect()
                 })
-                .take(10) // 最多显示10条结果
-                .cloned()
+            } else {
+                index.search(&terms, prefix_last, usize::MAX)
+            };
+            hits
+                .into_iter()
+                .filter_map(|hit| {
+                    let article = aid_map
+                        .with(|map| map.get(&hit.aid).copied())
+                        .and_then(|idx| articles.with(|articles| articles.get(idx).cloned()))?;
+                    query.matches(&article).then(|| SearchResult {
+                        title: highlight(article.title(), &hit.terms),
+                        snippet: excerpt(&index.excerpt_sources(&hit.aid), &hit.terms, SNIPPET_RADIUS),
+                        score: hit.score,
+                        article,
+                    })
+                })
+                .take(SEARCH_LIMIT)
                 .collect()
         })
     })
@@ -219,9 +506,173 @@ pub fn clear_filters(selected_tags: RwSignal<HashSet<String>>, selected_categori
 }
 
 pub fn provide_app_context() { 
-    provide_context(AppState::new());
+    let state = AppState::new();
+    if let Some(preloaded) = use_context::<Preloaded>().or_else(Preloaded::from_document) {
+        state.apply_preloaded(preloaded);
+    }
+    provide_context(state);
+}
+
+/// 预先生成的搜索索引的下载状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrebuiltIndex {
+    /// 站点没有提供，或已经下载过
+    #[default]
+    Unavailable,
+    /// 站点提供了索引，打开搜索面板时下载
+    Available,
+    Loading,
+}
+
+/// 嵌入服务端渲染页面的预加载数据的 `<script>` id
+pub const PRELOADED_ID: &str = "rusite-preloaded";
+
+/// 服务端渲染时预先加载的数据。服务端通过 context 提供给 `provide_app_context`，
+/// 同时序列化到页面中，浏览器水合时读取同一份数据，保证两端渲染结果一致。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Preloaded {
+    pub articles: Vec<ArticleInfo>,
+    pub tags: Vec<Tag>,
+    pub categories: Vec<Category>,
+    /// 当前页面用到的文章详情
+    pub details: Vec<PreloadedDetails>,
+    /// 站点是否提供了预先生成的搜索索引（静态导出）
+    #[serde(default)]
+    pub search_index: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreloadedDetails {
+    pub aid: String,
+    pub content: String,
+    pub comments: Vec<Comment>,
+}
+
+impl Preloaded {
+    /// 加载渲染 `path` 所需的数据：文章列表、标签、分类，文章页另外加载正文和评论
+    pub async fn fetch(path: &str) -> Result<Self, ApiError> {
+        let (articles, tags, categories) = fetch_all().await?;
+        let mut details = Vec::new();
+        let aid = path.strip_prefix("/blog/").map(|aid| aid.trim_end_matches('/'));
+        if let Some(info) = aid.and_then(|aid| articles.iter().find(|info| info.aid == aid)) {
+            // 正文加载失败时页面仍可渲染，交给浏览器按需加载
+            match get_article_details(info.clone()).await {
+                Ok(article) => details.push(PreloadedDetails {
+                    aid: article.aid(),
+                    content: article.content().to_string(),
+                    comments: article.comments().to_vec(),
+                }),
+                Err(err) => log::warn!("failed to preload article {}: {err}", info.aid),
+            }
+        }
+        Ok(Self { articles, tags, categories, details, search_index: false })
+    }
+    /// 序列化为放在页面中的 `<script>`，转义 `<` 以免内容中的 `</script>` 提前结束脚本
+    pub fn to_script(&self) -> Result<String, serde_json::Error> {
+        let json = serde_json::to_string(self)?.replace('<', "\\u003c");
+        Ok(format!(r#"<script id="{PRELOADED_ID}" type="application/json">{json}</script>"#))
+    }
+    /// 读取服务端嵌入页面的数据
+    #[cfg(feature = "hydrate")]
+    pub fn from_document() -> Option<Self> {
+        let text = document().get_element_by_id(PRELOADED_ID)?.text_content()?;
+        serde_json::from_str(&text).ok()
+    }
+    #[cfg(not(feature = "hydrate"))]
+    pub fn from_document() -> Option<Self> {
+        None
+    }
 }
 
 pub fn use_app() -> AppState { 
     use_context::<AppState>().expect("AppState should be provided")
-}
\ No newline at end of file
+}
+
+/// 登录状态
+#[derive(Clone, Copy, Debug)]
+pub struct AuthState {
+    pub user: RwSignal<Option<User>>,
+    /// 本地会话是否已恢复。服务端渲染和水合时会话要等挂载后才恢复，
+    /// 以保证首次渲染与服务端一致
+    pub restored: RwSignal<bool>,
+}
+
+impl Default for AuthState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthState {
+    /// 从 localStorage 恢复会话，并在请求返回 401 且无法刷新时自动退出登录
+    pub fn new() -> Self {
+        let restored = cfg!(feature = "csr");
+        let user = RwSignal::new(if restored { session::restore().map(|session| session.user) } else { None });
+        // 服务端没有本地会话，每个请求的 owner 渲染完就会销毁，不必注册
+        if !cfg!(feature = "ssr") {
+            session::set_unauthorized_handler(move || {
+                user.try_set(None);
+            });
+        }
+        Self { user, restored: RwSignal::new(restored) }
+    }
+    pub fn is_logged_in(&self) -> bool {
+        self.user.with(Option::is_some)
+    }
+    /// 供受保护的路由判断，会话尚未恢复时为 `None`
+    pub fn logged_in(&self) -> Option<bool> {
+        self.restored.get().then(|| self.is_logged_in())
+    }
+    pub async fn login(&self, request: LoginRequest) -> Result<User, ApiError> {
+        let session = auth::login(&request).await?;
+        self.user.set(Some(session.user.clone()));
+        Ok(session.user)
+    }
+    /// 注册并使用同一组凭据登录
+    pub async fn register(&self, request: UserCreateRequest) -> Result<User, ApiError> {
+        auth::register(&request).await?;
+        self.login(LoginRequest {
+            username: request.username,
+            password: request.password,
+        }).await
+    }
+    pub async fn logout(&self) {
+        if let Err(err) = auth::logout().await {
+            log::warn!("logout request failed: {err}");
+        }
+        self.user.set(None);
+    }
+    /// 启动时校验恢复的会话：令牌过期则先刷新，再向后端确认当前用户
+    pub async fn restore(&self) {
+        if !self.restored.get_untracked() {
+            self.user.set(session::restore().map(|session| session.user));
+            self.restored.set(true);
+        }
+        let Some(current) = session::current() else {
+            return;
+        };
+        if current.token.is_expired() && auth::refresh().await.is_err() {
+            session::expire();
+            return;
+        }
+        match auth::get_current_user().await {
+            Ok(user) => {
+                if let Some(mut session) = session::current() {
+                    session.user = user.clone();
+                    session::save(&session);
+                }
+                self.user.set(Some(user));
+            }
+            // 网络问题时保留本地会话，401 已在 fetch_api 中处理
+            Err(err) => log::warn!("failed to verify session: {err}"),
+        }
+    }
+}
+
+pub fn provide_auth_context() {
+    provide_context(AuthState::new());
+}
+
+pub fn use_auth() -> AuthState {
+    use_context::<AuthState>().expect("AuthState should be provided")
+}
@@ -1,30 +1,80 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::{HashMap, HashSet}};
 
 use chrono::{DateTime, Utc};
-use chrono_tz::Asia::Shanghai;
-use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use chrono_tz::{Asia::Shanghai, Tz};
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
 use regex::Regex;
 use once_cell::sync::Lazy;
 
-use crate::models::markdown::{FrontMatter, Heading, MarkdownContent};
+use crate::{
+    highlight::{highlight_code, CodeFence},
+    math::render_math,
+    models::markdown::{FrontMatter, Heading, MarkdownContent},
+};
 
 pub fn format_date_cn(date: DateTime<Utc>) -> String {
     date.with_timezone(&Shanghai).format("%Y-%m-%d %H:%M").to_string()
 }
 
+// 浏览器 localStorage 读写，非浏览器环境（如构建脚本）下为空操作
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn local_storage() -> Option<web_sys::Storage> {
+    None
+}
+
+pub fn storage_get(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok().flatten()
+}
+
+pub fn storage_set(key: &str, value: &str) -> bool {
+    local_storage().is_some_and(|storage| storage.set_item(key, value).is_ok())
+}
+
+pub fn storage_remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
+
+const TIMEZONE_KEY: &str = "rusite:timezone";
+/// 读者未设置时区时按上海时间显示
+pub const DEFAULT_TIMEZONE: Tz = Shanghai;
+
+/// 读取读者设置的时区，未设置或无法识别时返回默认时区
+pub fn load_timezone() -> Tz {
+    storage_get(TIMEZONE_KEY)
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(DEFAULT_TIMEZONE)
+}
+
+pub fn save_timezone(timezone: Tz) {
+    storage_set(TIMEZONE_KEY, timezone.name());
+}
+
 // 静态正则用于提取 Front Matter
 static FRONT_MATTER_REGEX: Lazy<Regex> = Lazy::new(|| {
     // Regex::new(r"(?s)^---\s*\n(.*?)\n---\s*\n(.*)$").unwrap()
     Regex::new(r"(?s)^(?:---\s*\n(.*?)\n---\s*\n)?(.*)$").unwrap()
 });
 
-// 解析 Markdown 内容
+// 解析 Markdown 内容，输出的 HTML 已按文章白名单清洗
 pub fn parse_markdown(content: &str) -> Option<MarkdownContent> {
+    parse_markdown_with(content, &SanitizeConfig::article())
+}
+
+// 解析 Markdown 内容，并用指定的白名单清洗 HTML
+pub fn parse_markdown_with(content: &str, config: &SanitizeConfig) -> Option<MarkdownContent> {
     // 解析 Front Matter
     let (front_matter, markdown_content) = parse_front_matter(content);
     
     // 生成 TOC 并渲染 HTML
     let (html_content, toc) = render_markdown_with_toc(&markdown_content);
+    let html_content = sanitize_html(&html_content, config);
     
     Some(MarkdownContent {
         front_matter,
@@ -34,7 +84,7 @@ pub fn parse_markdown(content: &str) -> Option<MarkdownContent> {
 }
 
 // 解析 Front Matter
-fn parse_front_matter(content: &str) -> (Option<FrontMatter>, String) {
+pub fn parse_front_matter(content: &str) -> (Option<FrontMatter>, String) {
     if let Some(captures) = FRONT_MATTER_REGEX.captures(content) {
         let markdown_content = captures
             .get(2)
@@ -64,119 +114,349 @@ pub fn render_markdown_with_toc(markdown: &str) -> (String, Vec<Heading>) {
     let mut toc = Vec::new();
     let mut html_output = String::new();
     let mut heading_id_counter: HashMap<String, u32> = HashMap::new();
+    let mut events: Vec<Event> = Vec::new();
+    // 当前标题的 Start 事件下标，用于在标题结束时补上 id
+    let mut heading_start: Option<usize> = None;
+    // 正在收集的代码块，结束时整体高亮
+    let mut code_block: Option<(CodeFence, String)> = None;
     // 处理事件并生成 TOC
-    let events = parser.map(|event| {
+    for event in parser {
         match &event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let fence = match kind {
+                    CodeBlockKind::Fenced(info) => CodeFence::parse(info),
+                    CodeBlockKind::Indented => CodeFence::default(),
+                };
+                code_block = Some((fence, String::new()));
+                continue;
+            }
+            Event::Text(text) if code_block.is_some() => {
+                if let Some((_, code)) = code_block.as_mut() {
+                    code.push_str(text);
+                }
+                continue;
+            }
+            Event::InlineMath(tex) | Event::DisplayMath(tex) => {
+                let display = matches!(event, Event::DisplayMath(_));
+                events.push(Event::InlineHtml(CowStr::from(render_math(tex, display))));
+                continue;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((fence, code)) = code_block.take() {
+                    events.push(Event::Html(CowStr::from(highlight_code(&code, &fence))));
+                }
+                continue;
+            }
             Event::Start(Tag::Heading{level, ..}) => {
                 // 开始处理标题
+                heading_start = Some(events.len());
                 toc.push(Heading {
                     id: String::new(),
                     text: String::new(),
                     level: *level as u32,
                 });
             }
-            Event::Text(text) if !toc.is_empty() => {
+            Event::Text(text) | Event::Code(text) if heading_start.is_some() => {
                 // 收集标题文本
                 if let Some(last) = toc.last_mut() {
                     last.text.push_str(text);
                 }
             }
-            Event::End(TagEnd::Heading(level)) if !toc.is_empty() => {
+            Event::End(TagEnd::Heading(_)) => {
                 // 结束标题处理，生成 ID
-                if let Some(heading) = toc.last_mut() {
-                    let base_id = heading.text.to_lowercase()
+                if let (Some(start), Some(heading)) = (heading_start.take(), toc.last_mut()) {
+                    let base_id = heading.text.trim().to_lowercase()
                         .replace(' ', "-")
-                        .replace(|c: char| !c.is_alphanumeric(), "");
+                        .replace(|c: char| !c.is_alphanumeric() && c != '-', "");
                     
                     let count = heading_id_counter.entry(base_id.clone()).or_insert(0);
                     *count += 1;
                     
                     heading.id = if *count > 1 {
-                        format!("{}-{}", base_id, count)
+                        format!("{CONTENT_ID_PREFIX}{base_id}-{count}")
                     } else {
-                        base_id
+                        format!("{CONTENT_ID_PREFIX}{base_id}")
                     };
-                    // 替换原始事件以添加 ID
-                    return Event::Html(
-                        format!("<h{} id=\"{}\">", level, heading.id).into()
-                    );
+                    // 替换原始的 Start 事件以添加 ID
+                    if let Event::Start(Tag::Heading { level, classes, attrs, .. }) = &events[start] {
+                        events[start] = Event::Start(Tag::Heading {
+                            level: *level,
+                            id: Some(heading.id.clone().into()),
+                            classes: classes.clone(),
+                            attrs: attrs.clone(),
+                        });
+                    }
                 }
             }
             _ => {}
         }
-        event
-    });
+        events.push(event);
+    }
     
     // 渲染 HTML
-    html::push_html(&mut html_output, events);
+    html::push_html(&mut html_output, events.into_iter());
     (html_output, toc)
 }
 
-// // 代码高亮初始化
-// #[wasm_bindgen]
-// pub fn init_code_highlighting() {
-//     let window = web_sys::window().expect("no global window exists");
-//     let document = window.document().expect("should have a document");
-    
-//     document.query_selector_all("pre code").unwrap().for_each(|element| {
-//         let element = element.unwrap();
-//         hljs_highlight_element(&element);
-//     });
-// }
-
-// // 数学公式渲染
-// #[wasm_bindgen]
-// pub fn render_math_formulas() {
-//     let window = web_sys::window().expect("no global window exists");
-//     let document = window.document().expect("should have a document");
-    
-//     // 渲染块级公式
-//     document.query_selector_all(".math-display").unwrap().for_each(|element| {
-//         let element = element.unwrap();
-//         render_math_element(&element, true);
-//     });
-    
-//     // 渲染行内公式
-//     document.query_selector_all(".math-inline").unwrap().for_each(|element| {
-//         let element = element.unwrap();
-//         render_math_element(&element, false);
-//     });
-// }
-
-// // 内部函数 - 高亮代码元素
-// fn hljs_highlight_element(element: &web_sys::Element) {
-//     let _ = js_sys::Reflect::apply(
-//         &js_sys::Function::from(js_sys::eval(
-//             "return hljs.highlightElement"
-//         ).unwrap()),
-//         &js_sys::global(),
-//         &[element.into()],
-//     );
-// }
-
-// // 内部函数 - 渲染数学元素
-// fn render_math_element(element: &web_sys::Element, display_mode: bool) {
-//     let text = element.text_content().unwrap();
-//     let options = js_sys::Object::new();
-//     let _ = js_sys::Reflect::set(
-//         &options,
-//         &"throwOnError".into(),
-//         &false.into(),
-//     );
-//     let _ = js_sys::Reflect::set(
-//         &options,
-//         &"displayMode".into(),
-//         &display_mode.into(),
-//     );
-    
-//     let katex = js_sys::eval("return katex").unwrap();
-//     let _ = js_sys::Reflect::apply(
-//         &js_sys::Function::from(js_sys::Reflect::get(&katex, &"render".into()).unwrap()),
-//         &katex,
-//         &[
-//             &text.into(),
-//             element.into(),
-//             &options.into(),
-//         ],
-//     );
-// }
\ No newline at end of file
+// 评论使用 Markdown 渲染，并按更严格的白名单清洗
+pub fn render_comment_html(content: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, Parser::new_ext(content, options));
+    sanitize_html(&html_output, &SanitizeConfig::comment())
+}
+
+/// 文章正文中 `id` 的前缀，避免正文中的元素覆盖页面自身的元素（DOM clobbering），
+/// 例如 `id="rusite-preloaded"`。生成的标题 id 已带有该前缀
+pub const CONTENT_ID_PREFIX: &str = "user-content-";
+
+/// HTML 清洗白名单：允许的标签、各标签允许的属性以及链接允许的 URL 协议。
+/// 不在白名单中的标签会被去掉（`script`/`style` 连同内容一起移除），
+/// 事件处理属性（`on*`）和 `style` 永远不会被保留。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanitizeConfig {
+    pub tags: HashSet<String>,
+    /// 标签名到允许属性的映射，`*` 表示所有标签通用的属性
+    pub attributes: HashMap<String, HashSet<String>>,
+    /// 取值受限的属性：`(标签名, 属性名)` 到允许取值的映射，取其它值时去掉该属性
+    pub attribute_values: HashMap<(String, String), HashSet<String>>,
+    /// 为所有 `id` 加上的前缀，页内锚点链接（`#...`）同步加上
+    pub id_prefix: Option<String>,
+    pub url_schemes: HashSet<String>,
+}
+
+fn string_set(items: &[&str]) -> HashSet<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+impl SanitizeConfig {
+    /// 文章正文的白名单，覆盖 pulldown-cmark 开启的表格、脚注、任务列表以及代码高亮、MathML 公式等输出
+    pub fn article() -> Self {
+        Self {
+            tags: string_set(&[
+                "a", "abbr", "b", "blockquote", "br", "code", "dd", "del", "details", "div", "dl", "dt",
+                "em", "h1", "h2", "h3", "h4", "h5", "h6", "hr", "i", "img", "input", "kbd", "li", "mark",
+                "ol", "p", "pre", "s", "span", "strong", "sub", "summary", "sup", "table", "tbody", "td",
+                "tfoot", "th", "thead", "tr", "u", "ul",
+                // MathML
+                "math", "semantics", "annotation", "mrow", "mi", "mn", "mo", "mtext", "mspace",
+                "mfrac", "msqrt", "mroot", "msub", "msup", "msubsup", "munder", "mover",
+                "munderover", "mtable", "mtr", "mtd",
+            ]),
+            attributes: HashMap::from([
+                ("*".to_string(), string_set(&["class", "title"])),
+                ("a".to_string(), string_set(&["href", "id"])),
+                ("img".to_string(), string_set(&["src", "alt", "width", "height"])),
+                ("input".to_string(), string_set(&["type", "checked", "disabled"])),
+                ("td".to_string(), string_set(&["align", "colspan", "rowspan"])),
+                ("th".to_string(), string_set(&["align", "colspan", "rowspan"])),
+                ("ol".to_string(), string_set(&["start"])),
+                ("span".to_string(), string_set(&["data-line"])),
+                ("math".to_string(), string_set(&["display"])),
+                ("annotation".to_string(), string_set(&["encoding"])),
+                ("mi".to_string(), string_set(&["mathvariant"])),
+                ("mn".to_string(), string_set(&["mathvariant"])),
+                ("mo".to_string(), string_set(&["fence", "stretchy", "largeop"])),
+                ("mspace".to_string(), string_set(&["width"])),
+                ("mfrac".to_string(), string_set(&["linethickness"])),
+                ("mover".to_string(), string_set(&["accent"])),
+                ("munder".to_string(), string_set(&["accentunder"])),
+                ("mtable".to_string(), string_set(&["columnalign"])),
+                ("div".to_string(), string_set(&["id"])),
+                ("sup".to_string(), string_set(&["id"])),
+                ("li".to_string(), string_set(&["id"])),
+                ("h1".to_string(), string_set(&["id"])),
+                ("h2".to_string(), string_set(&["id"])),
+                ("h3".to_string(), string_set(&["id"])),
+                ("h4".to_string(), string_set(&["id"])),
+                ("h5".to_string(), string_set(&["id"])),
+                ("h6".to_string(), string_set(&["id"])),
+            ]),
+            // 只用于任务列表的复选框
+            attribute_values: HashMap::from([
+                (("input".to_string(), "type".to_string()), string_set(&["checkbox"])),
+            ]),
+            id_prefix: Some(CONTENT_ID_PREFIX.to_string()),
+            url_schemes: string_set(&["http", "https", "mailto"]),
+        }
+    }
+
+    /// 评论的白名单：只保留基本的行内格式、代码和链接
+    pub fn comment() -> Self {
+        Self {
+            tags: string_set(&[
+                "a", "b", "blockquote", "br", "code", "del", "em", "i", "li", "ol", "p", "pre", "s",
+                "strong", "ul",
+            ]),
+            attributes: HashMap::from([
+                ("a".to_string(), string_set(&["href"])),
+            ]),
+            attribute_values: HashMap::new(),
+            id_prefix: None,
+            url_schemes: string_set(&["http", "https"]),
+        }
+    }
+
+    pub fn allow_tags(mut self, tags: &[&str]) -> Self {
+        self.tags.extend(tags.iter().map(|tag| tag.to_string()));
+        self
+    }
+
+    pub fn allow_attributes(mut self, tag: &str, attributes: &[&str]) -> Self {
+        self.attributes
+            .entry(tag.to_string())
+            .or_default()
+            .extend(attributes.iter().map(|attr| attr.to_string()));
+        self
+    }
+
+    pub fn restrict_attribute_values(mut self, tag: &str, attribute: &str, values: &[&str]) -> Self {
+        self.attribute_values.insert((tag.to_string(), attribute.to_string()), string_set(values));
+        self
+    }
+
+    pub fn allow_url_schemes(mut self, schemes: &[&str]) -> Self {
+        self.url_schemes.extend(schemes.iter().map(|scheme| scheme.to_string()));
+        self
+    }
+}
+
+// 按白名单清洗 HTML
+pub fn sanitize_html(html: &str, config: &SanitizeConfig) -> String {
+    // 事件处理属性和内联样式无论配置如何都不允许
+    let is_allowed_attr = |attr: &&str| !attr.starts_with("on") && *attr != "style";
+    let generic = config
+        .attributes
+        .get("*")
+        .map(|attrs| attrs.iter().map(String::as_str).filter(is_allowed_attr).collect())
+        .unwrap_or_default();
+    let tag_attributes = config
+        .attributes
+        .iter()
+        .filter(|(tag, _)| tag.as_str() != "*")
+        .map(|(tag, attrs)| {
+            (tag.as_str(), attrs.iter().map(String::as_str).filter(is_allowed_attr).filter(|attr| *attr != "rel").collect())
+        })
+        .collect();
+    let filter_config = config.clone();
+
+    ammonia::Builder::empty()
+        .tags(config.tags.iter().map(String::as_str).collect())
+        .clean_content_tags(HashSet::from(["script", "style"]))
+        .generic_attributes(generic)
+        .tag_attributes(tag_attributes)
+        .attribute_filter(move |element, attribute, value| filter_attribute(&filter_config, element, attribute, value))
+        .id_prefix(config.id_prefix.as_deref())
+        .url_schemes(config.url_schemes.iter().map(String::as_str).collect())
+        .link_rel(Some("noopener noreferrer"))
+        .clean(html)
+        .to_string()
+}
+
+/// 去掉取值不在 `attribute_values` 中的属性，并为页内锚点链接加上 id 前缀
+fn filter_attribute<'a>(config: &SanitizeConfig, element: &str, attribute: &str, value: &'a str) -> Option<Cow<'a, str>> {
+    if let Some(allowed) = config.attribute_values.get(&(element.to_string(), attribute.to_string())) {
+        return allowed.contains(value).then_some(Cow::Borrowed(value));
+    }
+    match (&config.id_prefix, value.strip_prefix('#')) {
+        (Some(prefix), Some(fragment)) if attribute == "href" && !fragment.starts_with(prefix.as_str()) => {
+            Some(Cow::Owned(format!("#{prefix}{fragment}")))
+        }
+        _ => Some(Cow::Borrowed(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(html: &str) -> String {
+        sanitize_html(html, &SanitizeConfig::article())
+    }
+
+    #[test]
+    fn removes_script_tags_with_content() {
+        let html = clean("<p>hi</p><script>alert('xss')</script>");
+        assert_eq!(html, "<p>hi</p>");
+    }
+
+    #[test]
+    fn removes_event_handler_attributes() {
+        let html = clean(r#"<img src="https://example.com/a.png" onerror="alert(1)"><p onclick="alert(2)">x</p>"#);
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("onclick"));
+        assert!(html.contains(r#"src="https://example.com/a.png""#));
+    }
+
+    #[test]
+    fn event_handlers_cannot_be_allowlisted() {
+        let config = SanitizeConfig::article().allow_attributes("p", &["onclick", "style"]);
+        let html = sanitize_html(r#"<p onclick="alert(1)" style="color:red">x</p>"#, &config);
+        assert_eq!(html, "<p>x</p>");
+    }
+
+    #[test]
+    fn strips_javascript_links() {
+        let html = clean(r#"<a href="javascript:alert(1)">link</a>"#);
+        assert!(!html.contains("javascript"));
+        let html = clean(r#"<a href="JaVaScRiPt:alert(1)">link</a>"#);
+        assert!(!html.to_lowercase().contains("javascript"));
+    }
+
+    #[test]
+    fn keeps_safe_links_and_adds_rel() {
+        let html = clean(r#"<a href="https://example.com">link</a>"#);
+        assert!(html.contains(r#"href="https://example.com""#));
+        assert!(html.contains(r#"rel="noopener noreferrer""#));
+    }
+
+    #[test]
+    fn markdown_raw_html_is_sanitized() {
+        let md = parse_markdown("# Title\n\n<script>alert(1)</script>\n\n[x](javascript:alert(1))").unwrap();
+        assert!(!md.html_content.contains("<script"));
+        assert!(!md.html_content.contains("javascript:"));
+        assert!(md.html_content.contains(r#"<h1 id="user-content-title">"#));
+    }
+
+    #[test]
+    fn only_checkbox_inputs_keep_their_type() {
+        let md = parse_markdown("- [x] done\n\n<input type=\"password\">").unwrap();
+        assert!(md.html_content.contains(r#"type="checkbox""#));
+        assert!(!md.html_content.contains("password"));
+    }
+
+    #[test]
+    fn prefixes_ids_and_fragment_links() {
+        let md = parse_markdown("# Intro\n\n[up](#intro) and a note[^1]\n\n[^1]: text\n\n<div id=\"rusite-preloaded\"></div>").unwrap();
+        assert_eq!(md.toc[0].id, "user-content-intro");
+        assert!(md.html_content.contains(r##"href="#user-content-intro""##));
+        assert!(md.html_content.contains(r##"href="#user-content-1""##));
+        assert!(md.html_content.contains(r#"id="user-content-1""#));
+        assert!(md.html_content.contains(r#"id="user-content-rusite-preloaded""#));
+        assert!(!md.html_content.contains(r#"id="rusite-preloaded""#));
+    }
+
+    #[test]
+    fn comment_config_is_stricter() {
+        let html = render_comment_html("<img src=\"https://example.com/a.png\">**bold**");
+        assert!(!html.contains("<img"));
+        assert!(html.contains("<strong>bold</strong>"));
+    }
+
+    #[test]
+    fn highlighted_code_survives_sanitizer() {
+        let md = parse_markdown("```rust {1}\nlet x = 1;\n```").unwrap();
+        assert!(md.html_content.contains(r#"<span class="code-line marked" data-line="1">"#));
+        assert!(md.html_content.contains("hl-keyword"));
+    }
+
+    #[test]
+    fn math_is_rendered_as_mathml() {
+        let md = parse_markdown("inline $x^2$ and\n\n$$\\frac{1}{2}$$\n\nbad $\\nope$").unwrap();
+        assert!(md.html_content.contains(r#"<math display="inline" class="math math-inline">"#));
+        assert!(md.html_content.contains("<msup><mi>x</mi><mn>2</mn></msup>"));
+        assert!(md.html_content.contains("<mfrac><mn>1</mn><mn>2</mn></mfrac>"));
+        assert!(md.html_content.contains(r#"<code class="math-error""#));
+    }
+}